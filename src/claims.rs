@@ -0,0 +1,821 @@
+//! Holder claim entrypoints and the per-holder and per-offering settings that shape claims:
+//! payout destinations, reinvestment, sub-accounts, streaming, ordering, delegates, delays
+//! and bonuses.
+
+use super::*;
+
+#[contractimpl]
+impl RevoraRevenueShare {
+    /// Claim aggregated revenue across multiple unclaimed periods.
+    ///
+    /// `max_periods` controls how many periods to process in one call
+    /// (0 = up to the network claim cap). Returns the total payout amount.
+    ///
+    /// Aggregation semantics:
+    /// - Periods are processed in `period_id` order (see `deposit_revenue`).
+    /// - Each holder's payout per period = `period_revenue * share_bps / 10000`.
+    /// - The holder's claim index advances regardless of zero-value periods.
+    /// - Capped at the network profile's `claim_periods` (50; 25 on Mainnet) per transaction for gas safety.
+    /// - Stops at the first period still inside the claim delay or paused by the admin;
+    ///   later periods wait until it clears.
+    /// - Pays the holder's payout address if one is set (`set_payout_address`).
+    /// - Records a `ClaimRecord` receipt (`get_claim_history_page`).
+    ///
+    /// Fails with `NoShare` if the holder has no share (never assigned or reduced to 0),
+    /// `NothingDeposited` if the offering has no deposited periods yet, and
+    /// `NoPendingClaims` if every deposited period has already been claimed.
+    ///
+    /// Holder authorization is bound to `(token, max_periods)`.
+    pub fn claim(
+        env: Env,
+        holder: Address,
+        token: Address,
+        max_periods: u32,
+    ) -> Result<i128, RevoraError> {
+        holder.require_auth_for_args((token.clone(), max_periods).into_val(&env));
+        let recipient = Self::payout_recipient(&env, &token, &holder);
+        Self::do_claim(env, holder, token, recipient, max_periods).map(|r| r.amount)
+    }
+
+    /// Claim from up to MAX_CLAIM_ALL_TOKENS (10) offerings in one transaction, with up to
+    /// `max_periods_per_token` periods each (0 = network claim cap). Returns `(token, amount)`
+    /// per entry of `tokens`, in order; offerings with nothing to claim yet report 0 as in
+    /// `claim_or_zero`. Any other error fails the whole call, so drop offerings whose
+    /// claims are blocked. `LimitReached` for more than 10 tokens.
+    ///
+    /// Holder authorization is bound to `(tokens, max_periods_per_token)`.
+    pub fn claim_all(
+        env: Env,
+        holder: Address,
+        tokens: Vec<Address>,
+        max_periods_per_token: u32,
+    ) -> Result<Vec<(Address, i128)>, RevoraError> {
+        holder.require_auth_for_args((tokens.clone(), max_periods_per_token).into_val(&env));
+        if tokens.len() > MAX_CLAIM_ALL_TOKENS {
+            return Err(RevoraError::LimitReached);
+        }
+        let mut payouts = Vec::new(&env);
+        for token in tokens.iter() {
+            let recipient = Self::payout_recipient(&env, &token, &holder);
+            let amount = match Self::do_claim(
+                env.clone(),
+                holder.clone(),
+                token.clone(),
+                recipient,
+                max_periods_per_token,
+            ) {
+                Ok(r) => r.amount,
+                Err(RevoraError::NothingDeposited) | Err(RevoraError::NoPendingClaims) => 0,
+                Err(e) => return Err(e),
+            };
+            payouts.push_back((token, amount));
+        }
+        Ok(payouts)
+    }
+
+    /// Same as `claim`, but returns a `ClaimResult` whose `more_pending` flag tells the
+    /// client that unclaimed periods remain (cap reached, or a delay/pause boundary), so
+    /// it can schedule a follow-up claim without probing for errors.
+    ///
+    /// Holder authorization is bound to `(token, max_periods)`.
+    pub fn claim_v2(
+        env: Env,
+        holder: Address,
+        token: Address,
+        max_periods: u32,
+    ) -> Result<ClaimResult, RevoraError> {
+        holder.require_auth_for_args((token.clone(), max_periods).into_val(&env));
+        let recipient = Self::payout_recipient(&env, &token, &holder);
+        Self::do_claim(env, holder, token, recipient, max_periods)
+    }
+
+    /// Same as `claim`, but succeeds with `0` instead of failing with `NothingDeposited` or
+    /// `NoPendingClaims`, so keepers sweeping many holders need not special-case holders
+    /// who are already up to date. Every other error is still returned.
+    ///
+    /// Holder authorization is bound to `(token, max_periods)`.
+    pub fn claim_or_zero(
+        env: Env,
+        holder: Address,
+        token: Address,
+        max_periods: u32,
+    ) -> Result<i128, RevoraError> {
+        holder.require_auth_for_args((token.clone(), max_periods).into_val(&env));
+        let recipient = Self::payout_recipient(&env, &token, &holder);
+        match Self::do_claim(env, holder, token, recipient, max_periods) {
+            Ok(r) => Ok(r.amount),
+            Err(RevoraError::NothingDeposited) | Err(RevoraError::NoPendingClaims) => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Claim on behalf of `holder` and pay `recipient`. Lets aggregator/router
+    /// contracts harvest Revora alongside other yield sources. Holder auth is
+    /// bound to `(token, recipient, max_periods)`, so a router cannot redirect funds.
+    /// Same rules and errors as `claim`.
+    pub fn claim_to(
+        env: Env,
+        holder: Address,
+        token: Address,
+        recipient: Address,
+        max_periods: u32,
+    ) -> Result<i128, RevoraError> {
+        holder
+            .require_auth_for_args((token.clone(), recipient.clone(), max_periods).into_val(&env));
+        Self::do_claim(env, holder, token, recipient, max_periods).map(|r| r.amount)
+    }
+
+    // ── Payout destination ─────────────────────────────────────
+
+    /// Redirect the holder's payouts on `token` (claims, pushes and proof claims) to
+    /// `destination`. Setting it to the holder's own address clears the redirect.
+    /// `claim_to` still pays its explicit recipient.
+    pub fn set_payout_address(
+        env: Env,
+        holder: Address,
+        token: Address,
+        destination: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        holder.require_auth();
+        let key = ClaimKey::PayoutAddress(token.clone(), holder.clone());
+        if destination == holder {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &destination);
+        }
+        env.events()
+            .publish((EVENT_PAYOUT_ADDRESS_SET, holder, token), destination);
+        Ok(())
+    }
+
+    /// Give `bps` of every `claim` on `token` to `recipient`, transferred directly during
+    /// the claim and recorded in the claim receipt. `None` or 0 bps removes the split.
+    /// Fails with `InvalidShareBps` above 10_000.
+    pub fn set_donation(
+        env: Env,
+        holder: Address,
+        token: Address,
+        recipient: Option<Address>,
+        bps: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        holder.require_auth();
+        if bps > 10_000 {
+            return Err(RevoraError::InvalidShareBps);
+        }
+        let key = ClaimKey::Donation(token.clone(), holder.clone());
+        match recipient.clone() {
+            Some(recipient) if bps > 0 => {
+                env.storage()
+                    .persistent()
+                    .set(&key, &DonationSplit { recipient, bps });
+            }
+            _ => env.storage().persistent().remove(&key),
+        }
+        env.events()
+            .publish((EVENT_DONATION_SET, holder, token), (recipient, bps));
+        Ok(())
+    }
+
+    // ── Claim reinvestment ─────────────────────────────────────
+
+    /// Let holders of `token` reinvest claims: each `rate` of payment token a reinvesting
+    /// holder claims goes to the issuer and raises their share by 1 bps. 0 disables it.
+    /// Fails with `InvalidAmount` below 0.
+    pub fn set_reinvest_rate(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        rate: i128,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        if rate < 0 {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let key = ClaimKey::ReinvestRate(token.clone());
+        if rate == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &rate);
+        }
+        env.events()
+            .publish((EVENT_REINVEST_RATE_SET, issuer, token), rate);
+        Ok(())
+    }
+
+    /// Opt in or out of reinvesting claims on `token`. While enabled, each claim converts
+    /// as many whole bps as the payout (after any donation) buys at the issuer's rate,
+    /// limited by the offering's unallocated share; the rest is paid out as usual. Claims
+    /// that leave periods unsettled pay out in full without reinvesting.
+    pub fn set_auto_reinvest(
+        env: Env,
+        holder: Address,
+        token: Address,
+        enabled: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        holder.require_auth();
+        let key = ClaimKey::AutoReinvest(token.clone(), holder.clone());
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        env.events()
+            .publish((EVENT_REINVEST_SET, holder, token), enabled);
+        Ok(())
+    }
+
+    // ── Custodial sub-accounts ─────────────────────────────────
+
+    /// Record how a custodian's share of `token` breaks down across its end clients.
+    /// The on-chain share stays with the custodian; claims additionally emit a
+    /// `sub_alloc` event attributing the payout to each sub-account. Entries need a
+    /// non-zero `bps` summing to 10_000 (`InvalidShareBps`) and unique ids
+    /// (`InvalidState`); at most 20 (`LimitReached`). An empty list removes the breakdown.
+    pub fn set_sub_accounts(
+        env: Env,
+        custodian: Address,
+        token: Address,
+        accounts: Vec<SubAccount>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        custodian.require_auth();
+        if accounts.len() > MAX_SUB_ACCOUNTS {
+            return Err(RevoraError::LimitReached);
+        }
+        let mut total: u32 = 0;
+        for (i, account) in accounts.iter().enumerate() {
+            if account.bps == 0 || account.bps > 10_000 {
+                return Err(RevoraError::InvalidShareBps);
+            }
+            total += account.bps;
+            for other in accounts.iter().skip(i + 1) {
+                if other.id == account.id {
+                    return Err(RevoraError::InvalidState);
+                }
+            }
+        }
+        let key = ClaimKey::SubAccounts(token.clone(), custodian.clone());
+        if accounts.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else if total != 10_000 {
+            return Err(RevoraError::InvalidShareBps);
+        } else {
+            env.storage().persistent().set(&key, &accounts);
+        }
+        env.events()
+            .publish((EVENT_SUB_ACCOUNTS_SET, custodian, token), accounts.len());
+        Ok(())
+    }
+
+    // ── Claim streaming ────────────────────────────────────────
+
+    /// Unlock each period's revenue linearly over `secs` from its deposit instead of all
+    /// at once; claims pay only the unlocked part and pick up the rest later. Applies to
+    /// every period not yet fully claimed. 0 turns streaming off.
+    pub fn set_stream_duration(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        secs: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        let key = ClaimKey::StreamSecs(token.clone());
+        if secs == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &secs);
+        }
+        env.events()
+            .publish((EVENT_STREAM_SET, issuer, token), secs);
+        Ok(())
+    }
+
+    /// Choose the order claims on `token` settle pending periods in when `max_periods`
+    /// (or the network cap) leaves some for later. Periods settled ahead of a holder's
+    /// oldest unclaimed period are never paid twice.
+    pub fn set_claim_order(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        order: ClaimOrder,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        let key = ClaimKey::Order(token.clone());
+        if order == ClaimOrder::OldestFirst {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &order);
+        }
+        env.events()
+            .publish((EVENT_CLAIM_ORDER, issuer, token), order);
+        Ok(())
+    }
+
+    /// Holder's own order for `token`, used while the offering is on
+    /// `ClaimOrder::HolderChoice`. Fails with `OfferingNotFound` for unknown offerings.
+    pub fn set_claim_order_preference(
+        env: Env,
+        holder: Address,
+        token: Address,
+        newest_first: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        holder.require_auth();
+        let key = ClaimKey::NewestFirst(token.clone(), holder.clone());
+        if newest_first {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        env.events()
+            .publish((EVENT_CLAIM_ORDER_PREF, holder, token), newest_first);
+        Ok(())
+    }
+
+    // ── Delegated claims ───────────────────────────────────────
+
+    /// Let `delegate` (a custodian or bot) trigger claims for `holder` on `token`.
+    /// Payouts still go to the holder (or their payout address). Replaces any existing
+    /// delegate.
+    pub fn set_claim_delegate(
+        env: Env,
+        holder: Address,
+        token: Address,
+        delegate: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        holder.require_auth();
+        env.storage().persistent().set(
+            &ClaimKey::Delegate(token.clone(), holder.clone()),
+            &delegate,
+        );
+        env.events()
+            .publish((EVENT_DELEGATE_SET, holder, token), delegate);
+        Ok(())
+    }
+
+    /// Revoke the holder's claim delegate for `token`. Idempotent.
+    pub fn revoke_claim_delegate(
+        env: Env,
+        holder: Address,
+        token: Address,
+    ) -> Result<(), RevoraError> {
+        holder.require_auth();
+        let key = ClaimKey::Delegate(token.clone(), holder.clone());
+        if let Some(delegate) = env.storage().persistent().get::<ClaimKey, Address>(&key) {
+            env.storage().persistent().remove(&key);
+            env.events()
+                .publish((EVENT_DELEGATE_REVOKED, holder, token), delegate);
+        }
+        Ok(())
+    }
+
+    /// Claim for `holder` as their registered delegate. Payout goes to the holder (or their
+    /// payout address); same
+    /// rules and errors as `claim`, plus `NotAuthorized` if `delegate` is not registered.
+    pub fn claim_for(
+        env: Env,
+        delegate: Address,
+        holder: Address,
+        token: Address,
+        max_periods: u32,
+    ) -> Result<i128, RevoraError> {
+        delegate.require_auth();
+        if Self::get_claim_delegate(env.clone(), token.clone(), holder.clone())
+            != Some(delegate.clone())
+        {
+            return Err(RevoraError::NotAuthorized);
+        }
+        let recipient = Self::payout_recipient(&env, &token, &holder);
+        let amount = Self::do_claim(
+            env.clone(),
+            holder.clone(),
+            token.clone(),
+            recipient,
+            max_periods,
+        )?
+        .amount;
+        env.events()
+            .publish((EVENT_DELEGATED_CLAIM, delegate, holder), (token, amount));
+        Ok(amount)
+    }
+
+    /// Claim `amount` for `period_id` with a Merkle proof against the period's root.
+    /// One claim per holder per period; total proof claims never exceed the deposit.
+    /// For a period that reuses a snapshot, `amount` and the leaf are the holder's
+    /// source-period entry; the payout is scaled by the two periods' revenue. Returns the
+    /// amount paid.
+    pub fn claim_with_proof(
+        env: Env,
+        holder: Address,
+        token: Address,
+        period_id: u64,
+        amount: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<i128, RevoraError> {
+        holder.require_auth_for_args((token.clone(), period_id, amount).into_val(&env));
+        Self::require_offering_not_paused(&env, &token)?;
+
+        if Self::is_barred(&env, &token, &holder) {
+            return Err(RevoraError::HolderBlacklisted);
+        }
+        Self::require_not_locked(&env, &token, &holder)?;
+        Self::require_kyc(&env, &token, &holder)?;
+        Self::require_restrictions_met(&env, &token, &holder)?;
+        Self::require_whitelisted(&env, &token, &holder)?;
+        if amount <= 0 {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let root: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&MerkleKey::Root(token.clone(), period_id))
+            .ok_or(RevoraError::InvalidProof)?;
+        let revenue: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+            .ok_or(RevoraError::NoPendingClaims)?;
+        if Self::is_period_paused(env.clone(), token.clone(), period_id) {
+            return Err(RevoraError::PeriodPaused);
+        }
+        let deposit_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodDepositTime(token.clone(), period_id))
+            .unwrap_or(0);
+        if Self::in_claim_delay(
+            &env,
+            &Self::claim_delay_schedule(&env, &token),
+            deposit_time,
+        ) {
+            return Err(RevoraError::ClaimDelayNotElapsed);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&EscrowKey::Swept(token.clone(), period_id))
+        {
+            return Err(RevoraError::NoPendingClaims);
+        }
+
+        let claimed_key = MerkleKey::Claimed(token.clone(), period_id, holder.clone());
+        if env.storage().persistent().has(&claimed_key) {
+            return Err(RevoraError::NoPendingClaims);
+        }
+
+        let source = Self::get_snapshot_source(env.clone(), token.clone(), period_id);
+        let leaf_period = source.unwrap_or(period_id);
+        let leaf = env
+            .crypto()
+            .sha256(&(token.clone(), leaf_period, holder.clone(), amount).to_xdr(&env))
+            .to_bytes();
+        if !Self::verify_merkle_proof(&env, &root, leaf, &proof) {
+            return Err(RevoraError::InvalidProof);
+        }
+        let amount = match source {
+            Some(source_period_id) => {
+                let source_revenue: i128 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::PeriodRevenue(token.clone(), source_period_id))
+                    .unwrap_or(0);
+                if source_revenue <= 0 {
+                    return Err(RevoraError::InvalidAmount);
+                }
+                amount
+                    .checked_mul(revenue)
+                    .ok_or(RevoraError::InvalidAmount)?
+                    / source_revenue
+            }
+            None => amount,
+        };
+
+        let total_key = MerkleKey::ClaimedTotal(token.clone(), period_id);
+        let claimed_total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        let new_total = claimed_total
+            .checked_add(amount)
+            .ok_or(RevoraError::InvalidAmount)?;
+        if new_total > revenue {
+            return Err(RevoraError::InvalidAmount);
+        }
+
+        env.storage().persistent().set(&claimed_key, &true);
+        env.storage().persistent().set(&total_key, &new_total);
+        Self::record_distributed(&env, &token, period_id, amount);
+
+        let payment_token: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PaymentToken(token.clone()))
+            .unwrap();
+        token::Client::new(&env, &payment_token).transfer(
+            &env.current_contract_address(),
+            &Self::payout_recipient(&env, &token, &holder),
+            &amount,
+        );
+
+        env.events()
+            .publish((EVENT_PROOF_CLAIM, holder, token), (period_id, amount));
+        Ok(amount)
+    }
+
+    /// Claim `holder`'s pro-rata part of a snapshot-backed period: `balance * revenue /
+    /// total_supply`. Once per holder per period. Same pause, blacklist and delay rules as
+    /// `claim_with_proof`; `NoShare` if the holder has no balance in the snapshot.
+    ///
+    /// Holder authorization is bound to `(token, period_id)`.
+    pub fn claim_snapshot(
+        env: Env,
+        holder: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<i128, RevoraError> {
+        holder.require_auth_for_args((token.clone(), period_id).into_val(&env));
+        Self::require_offering_not_paused(&env, &token)?;
+        if Self::is_barred(&env, &token, &holder) {
+            return Err(RevoraError::HolderBlacklisted);
+        }
+        Self::require_not_locked(&env, &token, &holder)?;
+        Self::require_kyc(&env, &token, &holder)?;
+        Self::require_restrictions_met(&env, &token, &holder)?;
+        Self::require_whitelisted(&env, &token, &holder)?;
+        let store = env.storage().persistent();
+        let snapshot_ref: u64 = store
+            .get(&SnapshotKey::ForPeriod(token.clone(), period_id))
+            .ok_or(RevoraError::InvalidState)?;
+        let revenue: i128 = store
+            .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+            .ok_or(RevoraError::NoPendingClaims)?;
+        if Self::is_period_paused(env.clone(), token.clone(), period_id) {
+            return Err(RevoraError::PeriodPaused);
+        }
+        let deposit_time: u64 = store
+            .get(&DataKey::PeriodDepositTime(token.clone(), period_id))
+            .unwrap_or(0);
+        if Self::in_claim_delay(
+            &env,
+            &Self::claim_delay_schedule(&env, &token),
+            deposit_time,
+        ) {
+            return Err(RevoraError::ClaimDelayNotElapsed);
+        }
+        if store.has(&EscrowKey::Swept(token.clone(), period_id)) {
+            return Err(RevoraError::NoPendingClaims);
+        }
+        let claimed_key = SnapshotKey::Claimed(token.clone(), period_id, holder.clone());
+        if store.has(&claimed_key) {
+            return Err(RevoraError::NoPendingClaims);
+        }
+
+        let balance =
+            Self::get_snapshot_balance(env.clone(), token.clone(), snapshot_ref, holder.clone());
+        if balance == 0 {
+            return Err(RevoraError::NoShare);
+        }
+        let supply: i128 = store
+            .get(&SnapshotKey::Supply(token.clone(), snapshot_ref))
+            .unwrap();
+        let amount = math::pro_rata(revenue, balance, supply).ok_or(RevoraError::InvalidAmount)?;
+
+        store.set(&claimed_key, &true);
+        Self::record_distributed(&env, &token, period_id, amount);
+        if amount > 0 {
+            let payment_token: Address = store.get(&DataKey::PaymentToken(token.clone())).unwrap();
+            token::Client::new(&env, &payment_token).transfer(
+                &env.current_contract_address(),
+                &Self::payout_recipient(&env, &token, &holder),
+                &amount,
+            );
+        }
+
+        env.events()
+            .publish((EVENT_SNAPSHOT_CLAIM, holder, token), (period_id, amount));
+        Ok(amount)
+    }
+
+    // ── Time-delayed claim configuration (#27) ──────────────────
+
+    /// Set per-offering claim delay in seconds. Only issuer may set. 0 = immediate claim.
+    /// A longer delay applies only to later deposits; a shorter one also to earlier
+    /// periods, so holders are never locked out of funds they could already claim. Fails
+    /// with `ClaimDelayOutOfRange` outside `ContractConfig`'s claim delay bounds (0 is
+    /// out of range only when the minimum is above 0).
+    pub fn set_claim_delay(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        delay_secs: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        // Verify offering exists and issuer is current
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+
+        issuer.require_auth();
+        let (min, max) = Self::claim_delay_bounds(&env);
+        if delay_secs < min || delay_secs > max {
+            return Err(RevoraError::ClaimDelayOutOfRange);
+        }
+        let mut schedule = Self::claim_delay_schedule(&env, &token);
+        // Earlier deposits keep their delay unless the new one is shorter.
+        let mut from = env.ledger().timestamp();
+        while let Some((step_from, secs)) = schedule.last() {
+            if secs < delay_secs {
+                break;
+            }
+            from = step_from;
+            schedule.pop_back();
+        }
+        schedule.push_back((from, delay_secs));
+        if schedule.len() > MAX_DELAY_STEPS {
+            schedule.remove(1);
+        }
+        let schedule_key = ClaimKey::DelaySchedule(token.clone());
+        if schedule.len() == 1 && delay_secs == 0 {
+            env.storage().persistent().remove(&schedule_key);
+        } else {
+            env.storage().persistent().set(&schedule_key, &schedule);
+        }
+        let key = DataKey::ClaimDelaySecs(token.clone());
+        env.storage().persistent().set(&key, &delay_secs);
+        env.events()
+            .publish((EVENT_CLAIM_DELAY_SET, issuer, token), delay_secs);
+        Ok(())
+    }
+
+    // ── Prompt-claim bonus ─────────────────────────────────────
+
+    /// Reward holders who claim within `window_secs` of a deposit with `bonus_bps` of
+    /// their payout on top, paid from the offering's bonus pool (`fund_bonus_pool`) while
+    /// it lasts. Issuer only; `bonus_bps` or `window_secs` of 0 turns the bonus off.
+    /// Fails with `InvalidAmount` above 1000 bps.
+    pub fn set_claim_bonus(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        bonus_bps: u32,
+        window_secs: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        if bonus_bps > MAX_CLAIM_BONUS_BPS {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let key = ClaimKey::Bonus(token.clone());
+        if bonus_bps == 0 || window_secs == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(
+                &key,
+                &ClaimBonus {
+                    bonus_bps,
+                    window_secs,
+                },
+            );
+        }
+        env.events()
+            .publish((EVENT_BONUS_SET, issuer, token), (bonus_bps, window_secs));
+        Ok(())
+    }
+
+    // ── Emergency withdrawal ───────────────────────────────────
+
+    /// Last-resort exit once the contract has been frozen for EMERGENCY_WITHDRAW_DELAY_SECS
+    /// (180 days), so an abandoned admin cannot strand holders behind pauses or invariant
+    /// freezes nobody can lift. Pays `holder`'s remaining share of up to the network claim
+    /// cap of their oldest unclaimed periods, ignoring global, offering and period pauses,
+    /// invariant freezes, claim delays and streaming; no bonus, donation or reinvestment
+    /// applies. Blacklisted holders stay barred.
+    ///
+    /// Once per holder and offering, and multisig: `holder` and every one of `approvers`
+    /// must authorize, each approver must be a safety address or `Pauser`, and there must
+    /// be at least a majority of the safety addresses (`len / 2 + 1`) distinct approvers,
+    /// so no single compromised key can release funds. Too few or repeated approvers fail
+    /// with `NotAuthorized`. Payments are recorded like `distribute_batch` pushes, so later
+    /// claims never pay a period twice. Fails with `EmergencyUnavailable` before the window
+    /// opens (or for freezes recorded before freeze times were kept) and on a second
+    /// withdrawal. Returns the amount paid.
+    pub fn emergency_withdraw(
+        env: Env,
+        holder: Address,
+        approvers: Vec<Address>,
+        token: Address,
+    ) -> Result<i128, RevoraError> {
+        holder.require_auth();
+        let required = Self::get_safety_addresses(env.clone()).len() / 2 + 1;
+        if approvers.len() < required {
+            return Err(RevoraError::NotAuthorized);
+        }
+        for (i, approver) in approvers.iter().enumerate() {
+            if approvers.first_index_of(&approver) != Some(i as u32) {
+                return Err(RevoraError::NotAuthorized);
+            }
+            approver.require_auth();
+            Self::require_safety(&env, &approver)?;
+        }
+        let frozen_at: Option<u64> = env.storage().instance().get(&ClaimKey::FrozenAt);
+        let open = Self::is_frozen(env.clone())
+            && frozen_at.is_some_and(|at| {
+                env.ledger().timestamp() >= at.saturating_add(EMERGENCY_WITHDRAW_DELAY_SECS)
+            });
+        let done_key = ClaimKey::EmergencyWithdrawn(token.clone(), holder.clone());
+        if !open || env.storage().persistent().has(&done_key) {
+            return Err(RevoraError::EmergencyUnavailable);
+        }
+        if Self::get_current_issuer(&env, &token).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        if Self::is_barred(&env, &token, &holder) {
+            return Err(RevoraError::HolderBlacklisted);
+        }
+
+        let store = env.storage().persistent();
+        let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+        let balance = Self::live_balance(&env, &token, &holder);
+        let period_count: u32 = store.get(&DataKey::PeriodCount(token.clone())).unwrap_or(0);
+        let start_idx: u32 = store
+            .get(&DataKey::LastClaimedIdx(token.clone(), holder.clone()))
+            .unwrap_or(0);
+        let claim_cap = Self::get_network_limits(env.clone()).claim_periods;
+        let end = core::cmp::min(period_count, start_idx.saturating_add(claim_cap));
+
+        let mut total: i128 = 0;
+        let mut periods: u32 = 0;
+        for i in start_idx..end {
+            let period_id: u64 = store.get(&DataKey::PeriodEntry(token.clone(), i)).unwrap();
+            let owed = Self::share_payout(&env, &token, period_id, &holder, share_bps, balance);
+            // Never pay a swept period, or beyond what a period still escrows.
+            let escrow = Self::get_period_escrow(env.clone(), token.clone(), period_id);
+            let amount = core::cmp::min(owed, escrow.deposited - escrow.distributed);
+            if escrow.closed || amount <= 0 {
+                continue;
+            }
+            let paid_key = DataKey::PushPaid(token.clone(), period_id, holder.clone());
+            let paid: i128 = store.get(&paid_key).unwrap_or(0);
+            store.set(&paid_key, &(paid + amount));
+            Self::record_distributed(&env, &token, period_id, amount);
+            total += amount;
+            periods += 1;
+        }
+
+        store.set(&done_key, &true);
+        if total > 0 {
+            let payment_token: Address = store.get(&DataKey::PaymentToken(token.clone())).unwrap();
+            let recipient = Self::payout_recipient(&env, &token, &holder);
+            token::Client::new(&env, &payment_token).transfer(
+                &env.current_contract_address(),
+                &recipient,
+                &total,
+            );
+        }
+        env.events().publish(
+            (EVENT_EMERGENCY_WITHDRAW, holder, token),
+            (approvers, total, periods),
+        );
+        Ok(total)
+    }
+}
@@ -0,0 +1,555 @@
+//! Compliance entrypoints: offering and global blacklists, whitelists, lockups, KYC and
+//! investor restrictions.
+
+use super::*;
+
+#[contractimpl]
+impl RevoraRevenueShare {
+    /// Add `investor` to the per-offering blacklist for `token`. Idempotent.
+    /// Once the offering has a compliance officer, only the issuer or the officer may call
+    /// this (`NotAuthorized` otherwise). Fails with `BlacklistLocked` after `lock_blacklist`.
+    pub fn blacklist_add(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        caller.require_auth();
+        Self::require_blacklist_manager(&env, &caller, &token)?;
+        if Self::is_blacklist_locked(env.clone(), token.clone()) {
+            return Err(RevoraError::BlacklistLocked);
+        }
+
+        let key = DataKey::Blacklist(token.clone());
+        let mut map: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(&env));
+
+        if !map.contains_key(investor.clone()) {
+            Self::update_metrics(&env, |m| {
+                m.blacklist_entries = m.blacklist_entries.saturating_add(1)
+            });
+        }
+        map.set(investor.clone(), true);
+        env.storage().persistent().set(&key, &map);
+
+        env.events()
+            .publish((EVENT_BL_ADD, token, caller), investor);
+        Ok(())
+    }
+
+    /// Remove `investor` from the per-offering blacklist for `token`. Idempotent.
+    /// Restricted like `blacklist_add`.
+    pub fn blacklist_remove(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        caller.require_auth();
+        Self::require_blacklist_manager(&env, &caller, &token)?;
+
+        let key = DataKey::Blacklist(token.clone());
+        let mut map: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(&env));
+
+        if map.remove(investor.clone()).is_some() {
+            Self::update_metrics(&env, |m| {
+                m.blacklist_entries = m.blacklist_entries.saturating_sub(1)
+            });
+        }
+        env.storage().persistent().set(&key, &map);
+
+        env.events()
+            .publish((EVENT_BL_REM, token, caller), investor);
+        Ok(())
+    }
+
+    /// Add up to 50 investors to `token`'s blacklist in one call (`LimitReached` above).
+    /// Restricted like `blacklist_add`. Emits one `bl_addb` event with the batch size and
+    /// how many were new; returns the number added.
+    pub fn blacklist_add_bulk(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investors: Vec<Address>,
+    ) -> Result<u32, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        caller.require_auth();
+        Self::require_blacklist_manager(&env, &caller, &token)?;
+        if Self::is_blacklist_locked(env.clone(), token.clone()) {
+            return Err(RevoraError::BlacklistLocked);
+        }
+        if investors.len() > MAX_LIST_BATCH {
+            return Err(RevoraError::LimitReached);
+        }
+
+        let key = DataKey::Blacklist(token.clone());
+        let mut map: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(&env));
+        let mut added: u32 = 0;
+        for investor in investors.iter() {
+            if !map.contains_key(investor.clone()) {
+                added += 1;
+            }
+            map.set(investor, true);
+        }
+        env.storage().persistent().set(&key, &map);
+        Self::update_metrics(&env, |m| {
+            m.blacklist_entries = m.blacklist_entries.saturating_add(added)
+        });
+
+        env.events()
+            .publish((EVENT_BL_ADD_BULK, token, caller), (investors.len(), added));
+        Ok(added)
+    }
+
+    /// Remove up to 50 investors from `token`'s blacklist in one call. Restricted like
+    /// `blacklist_remove`. Emits one `bl_remb` event; returns the number removed.
+    pub fn blacklist_remove_bulk(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investors: Vec<Address>,
+    ) -> Result<u32, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        caller.require_auth();
+        Self::require_blacklist_manager(&env, &caller, &token)?;
+        if investors.len() > MAX_LIST_BATCH {
+            return Err(RevoraError::LimitReached);
+        }
+
+        let key = DataKey::Blacklist(token.clone());
+        let mut map: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(&env));
+        let mut removed: u32 = 0;
+        for investor in investors.iter() {
+            if map.remove(investor).is_some() {
+                removed += 1;
+            }
+        }
+        env.storage().persistent().set(&key, &map);
+        Self::update_metrics(&env, |m| {
+            m.blacklist_entries = m.blacklist_entries.saturating_sub(removed)
+        });
+
+        env.events().publish(
+            (EVENT_BL_REM_BULK, token, caller),
+            (investors.len(), removed),
+        );
+        Ok(removed)
+    }
+
+    /// Delegate blacklist management for an offering to a compliance officer, or clear it
+    /// with `None`. While an officer is set, blacklist edits are limited to the issuer and the
+    /// officer. The officer gains no other rights: issuer-gated entrypoints (deposits, shares,
+    /// fees, transfers) still require the issuer.
+    pub fn set_compliance_officer(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        officer: Option<Address>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let key = ComplianceKey::Officer(token.clone());
+        match &officer {
+            Some(addr) => env.storage().persistent().set(&key, addr),
+            None => env.storage().persistent().remove(&key),
+        }
+        env.events()
+            .publish((EVENT_COMPLIANCE_OFFICER_SET, issuer, token), officer);
+        Ok(())
+    }
+
+    /// Permanently disable blacklist additions for an offering, e.g. one marketed as
+    /// censorship-resistant. One-way: there is no unlock. Removals stay allowed so existing
+    /// entries can still be cleared.
+    pub fn lock_blacklist(env: Env, issuer: Address, token: Address) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        env.storage()
+            .persistent()
+            .set(&ComplianceKey::BlacklistLocked(token.clone()), &true);
+        env.events()
+            .publish((EVENT_BLACKLIST_LOCKED, issuer), token);
+        Ok(())
+    }
+
+    // ── Whitelist ──────────────────────────────────────────────
+
+    /// Add `investor` to the whitelist for `token`. Idempotent. Unlike the blacklist,
+    /// only the issuer, the compliance officer and `ComplianceOfficer` role holders may
+    /// edit it. Takes effect for claims and share assignments once enabled.
+    pub fn whitelist_add(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        caller.require_auth();
+        Self::require_whitelist_manager(&env, &caller, &token)?;
+
+        let key = ComplianceKey::Whitelist(token.clone());
+        let mut map: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(&env));
+        map.set(investor.clone(), true);
+        env.storage().persistent().set(&key, &map);
+
+        env.events()
+            .publish((EVENT_WL_ADD, token, caller), investor);
+        Ok(())
+    }
+
+    /// Remove `investor` from the whitelist for `token`. Idempotent. Restricted like
+    /// `whitelist_add`.
+    pub fn whitelist_remove(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        caller.require_auth();
+        Self::require_whitelist_manager(&env, &caller, &token)?;
+
+        let key = ComplianceKey::Whitelist(token.clone());
+        let mut map: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(&env));
+        map.remove(investor.clone());
+        env.storage().persistent().set(&key, &map);
+
+        env.events()
+            .publish((EVENT_WL_REM, token, caller), investor);
+        Ok(())
+    }
+
+    /// Add up to 50 investors to `token`'s whitelist in one call (`LimitReached` above).
+    /// Restricted like `whitelist_add`. Emits one `wl_addb` event; returns the number
+    /// added.
+    pub fn whitelist_add_bulk(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investors: Vec<Address>,
+    ) -> Result<u32, RevoraError> {
+        Self::edit_whitelist_bulk(env, caller, token, investors, true)
+    }
+
+    /// Remove up to 50 investors from `token`'s whitelist in one call. Restricted like
+    /// `whitelist_remove`. Emits one `wl_remb` event; returns the number removed.
+    pub fn whitelist_remove_bulk(
+        env: Env,
+        caller: Address,
+        token: Address,
+        investors: Vec<Address>,
+    ) -> Result<u32, RevoraError> {
+        Self::edit_whitelist_bulk(env, caller, token, investors, false)
+    }
+
+    /// Turn whitelist enforcement on or off for `token` (issuer only). While on, claims
+    /// and non-zero share assignments for holders not on the whitelist fail with
+    /// `HolderNotWhitelisted`. The blacklist still applies to whitelisted holders.
+    pub fn set_whitelist_enabled(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        enabled: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let key = ComplianceKey::WhitelistEnabled(token.clone());
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        env.events()
+            .publish((EVENT_WL_ENABLED, issuer, token), enabled);
+        Ok(())
+    }
+
+    /// Add `investor` to the platform-wide blacklist (admin or `ComplianceOfficer`).
+    /// Listed investors cannot claim, receive pushed payouts or be given a share on any
+    /// offering, on top of each offering's own blacklist. Idempotent.
+    pub fn global_blacklist_add(
+        env: Env,
+        caller: Address,
+        investor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        caller.require_auth();
+        Self::require_admin_or_role(&env, &caller, Role::ComplianceOfficer)?;
+        env.storage()
+            .persistent()
+            .set(&ComplianceKey::GlobalBlacklist(investor.clone()), &true);
+        env.events()
+            .publish((EVENT_GLOBAL_BL_ADD, caller), investor);
+        Ok(())
+    }
+
+    /// Remove `investor` from the platform-wide blacklist (admin or `ComplianceOfficer`).
+    /// Idempotent.
+    pub fn global_blacklist_remove(
+        env: Env,
+        caller: Address,
+        investor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        caller.require_auth();
+        Self::require_admin_or_role(&env, &caller, Role::ComplianceOfficer)?;
+        env.storage()
+            .persistent()
+            .remove(&ComplianceKey::GlobalBlacklist(investor.clone()));
+        env.events()
+            .publish((EVENT_GLOBAL_BL_REM, caller), investor);
+        Ok(())
+    }
+
+    /// Hold `holder`'s claims on `token` until `until` (ledger timestamp), e.g. pending a
+    /// KYC refresh; 0 lifts the hold. Limited to the issuer, the offering's compliance
+    /// officer and `ComplianceOfficer` role holders.
+    pub fn set_holder_lockup(
+        env: Env,
+        caller: Address,
+        token: Address,
+        holder: Address,
+        until: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        caller.require_auth();
+        Self::require_whitelist_manager(&env, &caller, &token)?;
+        let key = ComplianceKey::Lockup(token.clone(), holder.clone());
+        if until == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &until);
+        }
+        env.events()
+            .publish((EVENT_HOLDER_LOCKUP, token, caller), (holder, until));
+        Ok(())
+    }
+
+    /// Set or clear (`None`) the KYC attestation registry (admin only). The registry must
+    /// expose `has_attestation(holder: Address) -> bool`.
+    pub fn set_kyc_registry(
+        env: Env,
+        admin: Address,
+        registry: Option<Address>,
+    ) -> Result<(), RevoraError> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        Self::require_not_frozen(&env)?;
+        match &registry {
+            Some(registry) => env
+                .storage()
+                .persistent()
+                .set(&ComplianceKey::KycRegistry, registry),
+            None => env
+                .storage()
+                .persistent()
+                .remove(&ComplianceKey::KycRegistry),
+        }
+        env.events().publish((EVENT_KYC_REGISTRY, admin), registry);
+        Ok(())
+    }
+
+    /// Require (or stop requiring) holders of `token` to hold a registry attestation
+    /// before claiming or being given a share (issuer only). Turning it on fails with
+    /// `NotInitialized` while no registry is set.
+    pub fn set_kyc_required(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        required: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        let key = ComplianceKey::KycRequired(token.clone());
+        if required {
+            if Self::get_kyc_registry(env.clone()).is_none() {
+                return Err(RevoraError::NotInitialized);
+            }
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        env.events()
+            .publish((EVENT_KYC_REQUIRED, issuer, token), required);
+        Ok(())
+    }
+
+    /// Set the investor restrictions for `token` (issuer only). Holders then need a profile
+    /// that satisfies them to be given a share or to claim (`RestrictionViolated`). No
+    /// jurisdictions and `accredited_only` false lifts them; more than 32 jurisdictions
+    /// fail with `LimitReached`.
+    pub fn set_offering_restrictions(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        restrictions: Restrictions,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        if restrictions.allowed_jurisdictions.len() > MAX_JURISDICTIONS {
+            return Err(RevoraError::LimitReached);
+        }
+        let key = ComplianceKey::Restrictions(token.clone());
+        if restrictions.allowed_jurisdictions.is_empty() && !restrictions.accredited_only {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &restrictions);
+        }
+        env.events()
+            .publish((EVENT_RESTRICTIONS_SET, issuer, token), restrictions);
+        Ok(())
+    }
+
+    /// Record `holder`'s jurisdiction and accreditation for `token`. Limited to the issuer,
+    /// the offering's compliance officer and `ComplianceOfficer` role holders.
+    pub fn set_holder_profile(
+        env: Env,
+        caller: Address,
+        token: Address,
+        holder: Address,
+        jurisdiction: Symbol,
+        accredited: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        caller.require_auth();
+        Self::require_whitelist_manager(&env, &caller, &token)?;
+        let profile = HolderProfile {
+            jurisdiction: jurisdiction.clone(),
+            accredited,
+        };
+        env.storage().persistent().set(
+            &ComplianceKey::HolderProfile(token.clone(), holder.clone()),
+            &profile,
+        );
+        env.events().publish(
+            (EVENT_PROFILE_SET, token, caller),
+            (holder, jurisdiction, accredited),
+        );
+        Ok(())
+    }
+
+    /// Anchor the hash of `token`'s terms document (issuer only), replacing any earlier
+    /// version. With `required`, holders must `acknowledge_terms` for this exact hash before
+    /// being given a non-zero share (`TermsNotAcknowledged`); existing shares are kept.
+    pub fn set_terms_hash(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        hash: BytesN<32>,
+        required: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        env.storage().persistent().set(
+            &ComplianceKey::Terms(token.clone()),
+            &OfferingTerms {
+                hash: hash.clone(),
+                required,
+                set_at: env.ledger().timestamp(),
+            },
+        );
+        env.events()
+            .publish((EVENT_TERMS_SET, issuer, token), (hash, required));
+        Ok(())
+    }
+
+    /// Record that `holder` accepted `token`'s terms version `hash`, replacing their earlier
+    /// acknowledgement. `InvalidState` if the offering has no terms or `hash` is not the
+    /// current one, so holders cannot acknowledge terms they were not shown.
+    pub fn acknowledge_terms(
+        env: Env,
+        holder: Address,
+        token: Address,
+        hash: BytesN<32>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        holder.require_auth();
+        let terms = Self::get_offering_terms(env.clone(), token.clone())
+            .ok_or(RevoraError::InvalidState)?;
+        if terms.hash != hash {
+            return Err(RevoraError::InvalidState);
+        }
+        env.storage().persistent().set(
+            &ComplianceKey::TermsAck(token.clone(), holder.clone()),
+            &TermsAcknowledgement {
+                hash: hash.clone(),
+                acknowledged_at: env.ledger().timestamp(),
+            },
+        );
+        env.events().publish((EVENT_TERMS_ACK, holder, token), hash);
+        Ok(())
+    }
+}
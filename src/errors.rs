@@ -0,0 +1,185 @@
+//! Contract error codes.
+//!
+//! Numbering is stable and append-only: never reuse or renumber a code, since
+//! integrators match on the numeric value. Add new variants at the end with the
+//! next free code and list them in `ALL_CODES`.
+
+use soroban_sdk::contracterror;
+
+/// Centralized contract error codes. Auth failures are signaled by host panic (require_auth).
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[repr(u32)]
+pub enum RevoraError {
+    /// revenue_share_bps exceeded 10000 (100%).
+    InvalidRevenueShareBps = 1,
+    /// A count limit was reached: offerings per issuer or platform-wide, batch sizes,
+    /// list lengths.
+    LimitReached = 2,
+    /// Holder concentration exceeds configured limit and enforcement is enabled.
+    ConcentrationLimitExceeded = 3,
+    /// No offering found for the given (issuer, token) pair.
+    OfferingNotFound = 4,
+    /// Revenue already deposited for this period.
+    PeriodAlreadyDeposited = 5,
+    /// No unclaimed periods for this holder.
+    NoPendingClaims = 6,
+    /// Holder is blacklisted for this offering.
+    HolderBlacklisted = 7,
+    /// Holder share_bps exceeded 10000 (100%).
+    InvalidShareBps = 8,
+    /// Payment token does not match previously set token for this offering.
+    PaymentTokenMismatch = 9,
+    /// Contract is frozen; state-changing operations are disabled.
+    ContractFrozen = 10,
+    /// Revenue for this period is not yet claimable (delay not elapsed).
+    ClaimDelayNotElapsed = 11,
+    /// A transfer is already pending for this offering.
+    IssuerTransferPending = 12,
+    /// No transfer is pending for this offering.
+    NoTransferPending = 13,
+    /// Caller is not authorized to accept this transfer.
+    UnauthorizedTransferAccept = 14,
+    /// Payout asset does not match the configured payout asset for this offering.
+    PayoutAssetMismatch = 15,
+    /// Caller does not hold the role required for this operation (admin/safety).
+    NotAuthorized = 16,
+    /// Contract has not been initialized (admin or safety role not set).
+    NotInitialized = 17,
+    /// Contract is paused; state-mutating operations are disabled.
+    ContractPaused = 18,
+    /// total_supply passed to a distribution calculation was zero.
+    ZeroTotalSupply = 19,
+    /// `initialize` was already called.
+    AlreadyInitialized = 20,
+    /// Issuer reporting is suspended after reaching the strike threshold; admin review required.
+    ReportingSuspended = 21,
+    /// Amount must be strictly positive.
+    InvalidAmount = 22,
+    /// Operation is not allowed in the capital raise's current phase.
+    RaiseStateInvalid = 23,
+    /// Subscription would push the raise above its target (hard cap).
+    RaiseCapExceeded = 24,
+    /// Operation is not allowed in the target record's current state (e.g. window closed).
+    InvalidState = 25,
+    /// Holder shares of this offering are not transferable.
+    SharesNotTransferable = 26,
+    /// A party still has unclaimed deposited periods; claim before moving shares.
+    PendingClaimsOutstanding = 27,
+    /// The next claimable period is paused by the admin.
+    PeriodPaused = 28,
+    /// Merkle proof does not match the period's distribution root.
+    InvalidProof = 29,
+    /// Summed holder shares would exceed the offering's revenue_share_bps.
+    ShareAllocationExceeded = 30,
+    /// The offering has been closed by its issuer.
+    OfferingClosed = 31,
+    /// The holder has no share in the offering (never assigned, or reduced to 0).
+    NoShare = 32,
+    /// The offering has no deposited periods yet.
+    NothingDeposited = 33,
+    /// The offering is paused by its issuer or the admin.
+    OfferingPaused = 34,
+    /// An override report's expected previous amount no longer matches the stored one.
+    StaleOverride = 35,
+    /// Metadata exceeds the configured maximum length.
+    MetadataTooLarge = 36,
+    /// The issuer transfer proposal outlived `ContractConfig.transfer_ttl_secs`.
+    ProposalExpired = 37,
+    /// The offering's blacklist is permanently locked against additions.
+    BlacklistLocked = 38,
+    /// The offering was frozen after an invariant check failed; needs admin review.
+    OfferingFrozen = 39,
+    /// The offering's whitelist is enabled and the holder is not on it.
+    HolderNotWhitelisted = 40,
+    /// The offering token did not answer as a token contract at registration.
+    OfferingTokenUnavailable = 41,
+    /// A compliance hold blocks the holder's claims until `get_holder_lockup`.
+    HolderLocked = 42,
+    /// The offering requires a KYC attestation and the registry has none for the holder.
+    KycAttestationMissing = 43,
+    /// The holder's profile does not meet the offering's jurisdiction or accreditation
+    /// restrictions, or the holder has no profile.
+    RestrictionViolated = 44,
+    /// The claim delay is outside `ContractConfig`'s `min_claim_delay_secs` /
+    /// `max_claim_delay_secs`.
+    ClaimDelayOutOfRange = 45,
+    /// The offering has a period schedule and the period has not started (or is 0).
+    PeriodOffSchedule = 46,
+    /// The deposit, net of the platform fee, is below the holders' share of the reported
+    /// revenue and `ContractConfig.reject_underpayment` is on.
+    DepositBelowReport = 47,
+    /// Emergency withdrawal is closed: the contract is not frozen, has been frozen for
+    /// less than EMERGENCY_WITHDRAW_DELAY_SECS, or the holder already withdrew.
+    EmergencyUnavailable = 48,
+    /// The offering requires terms acknowledgement and the holder has not acknowledged
+    /// the current terms hash.
+    TermsNotAcknowledged = 49,
+}
+
+/// Every error code, in declaration order. Used to check uniqueness at compile time.
+pub(crate) const ALL_CODES: [u32; 49] = [
+    RevoraError::InvalidRevenueShareBps as u32,
+    RevoraError::LimitReached as u32,
+    RevoraError::ConcentrationLimitExceeded as u32,
+    RevoraError::OfferingNotFound as u32,
+    RevoraError::PeriodAlreadyDeposited as u32,
+    RevoraError::NoPendingClaims as u32,
+    RevoraError::HolderBlacklisted as u32,
+    RevoraError::InvalidShareBps as u32,
+    RevoraError::PaymentTokenMismatch as u32,
+    RevoraError::ContractFrozen as u32,
+    RevoraError::ClaimDelayNotElapsed as u32,
+    RevoraError::IssuerTransferPending as u32,
+    RevoraError::NoTransferPending as u32,
+    RevoraError::UnauthorizedTransferAccept as u32,
+    RevoraError::PayoutAssetMismatch as u32,
+    RevoraError::NotAuthorized as u32,
+    RevoraError::NotInitialized as u32,
+    RevoraError::ContractPaused as u32,
+    RevoraError::ZeroTotalSupply as u32,
+    RevoraError::AlreadyInitialized as u32,
+    RevoraError::ReportingSuspended as u32,
+    RevoraError::InvalidAmount as u32,
+    RevoraError::RaiseStateInvalid as u32,
+    RevoraError::RaiseCapExceeded as u32,
+    RevoraError::InvalidState as u32,
+    RevoraError::SharesNotTransferable as u32,
+    RevoraError::PendingClaimsOutstanding as u32,
+    RevoraError::PeriodPaused as u32,
+    RevoraError::InvalidProof as u32,
+    RevoraError::ShareAllocationExceeded as u32,
+    RevoraError::OfferingClosed as u32,
+    RevoraError::NoShare as u32,
+    RevoraError::NothingDeposited as u32,
+    RevoraError::OfferingPaused as u32,
+    RevoraError::StaleOverride as u32,
+    RevoraError::MetadataTooLarge as u32,
+    RevoraError::ProposalExpired as u32,
+    RevoraError::BlacklistLocked as u32,
+    RevoraError::OfferingFrozen as u32,
+    RevoraError::HolderNotWhitelisted as u32,
+    RevoraError::OfferingTokenUnavailable as u32,
+    RevoraError::HolderLocked as u32,
+    RevoraError::KycAttestationMissing as u32,
+    RevoraError::RestrictionViolated as u32,
+    RevoraError::ClaimDelayOutOfRange as u32,
+    RevoraError::PeriodOffSchedule as u32,
+    RevoraError::DepositBelowReport as u32,
+    RevoraError::EmergencyUnavailable as u32,
+    RevoraError::TermsNotAcknowledged as u32,
+];
+
+const fn codes_unique_and_ascending(codes: &[u32]) -> bool {
+    let mut i = 1;
+    while i < codes.len() {
+        if codes[i] <= codes[i - 1] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// Fails the build if any two variants share a code or a code is added out of order.
+const _: () = assert!(codes_unique_and_ascending(&ALL_CODES));
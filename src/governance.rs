@@ -0,0 +1,806 @@
+//! Contract administration entrypoints: initialization, upgrades and migration, config,
+//! roles, safety pauses, invariant freezes, platform fees, issuer strikes and testnet resets.
+
+use super::*;
+
+#[contractimpl]
+impl RevoraRevenueShare {
+    /// Initialize admin and optional safety role for emergency pause (#7).
+    /// Can only be called once; returns `AlreadyInitialized` on subsequent calls.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        safety: Option<Address>,
+    ) -> Result<(), RevoraError> {
+        if Self::read_admin(&env).is_some() {
+            return Err(RevoraError::AlreadyInitialized);
+        }
+        Self::write_instance(&env, &DataKey::Admin, &admin);
+        if let Some(s) = safety.clone() {
+            env.storage()
+                .persistent()
+                .set(&DataKey::SafetySet, &Vec::from_array(&env, [s]));
+        }
+        Self::write_instance(&env, &DataKey::Paused, &false);
+        env.storage().persistent().set(&DataKey::InitVersion, &1u32);
+        env.storage()
+            .persistent()
+            .set(&AdminKey::ContractVersion, &CONTRACT_VERSION);
+        env.events().publish((EVENT_INIT, admin.clone()), (safety,));
+        Ok(())
+    }
+
+    /// Second initialization step for config fields added after the original deployment.
+    /// Admin only; may run once. Never touches admin or safety, so it cannot be used to
+    /// re-initialize the contract. Contracts initialized before `InitVersion` existed are
+    /// treated as version 1.
+    pub fn initialize_v2(
+        env: Env,
+        caller: Address,
+        event_versioning: bool,
+    ) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        if Self::get_init_version(env.clone()) >= 2 {
+            return Err(RevoraError::AlreadyInitialized);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::EventVersioningEnabled, &event_versioning);
+        env.storage().persistent().set(&DataKey::InitVersion, &2u32);
+        env.events()
+            .publish((EVENT_INIT_V2, caller), (event_versioning,));
+        Ok(())
+    }
+
+    /// Third initialization step: record the network profile, which selects the
+    /// per-call limits (see `get_network_limits`). Admin only; may run once, after
+    /// `initialize` (and `initialize_v2`, if used, since steps only move forward).
+    /// Contracts without a profile use the Testnet limits.
+    pub fn initialize_v3(
+        env: Env,
+        caller: Address,
+        profile: NetworkProfile,
+    ) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        if Self::get_init_version(env.clone()) >= 3 {
+            return Err(RevoraError::AlreadyInitialized);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::NetworkProfile, &profile);
+        env.storage().persistent().set(&DataKey::InitVersion, &3u32);
+        env.events().publish((EVENT_INIT_V3, caller), (profile,));
+        Ok(())
+    }
+
+    /// Replace the contract's code with the uploaded WASM `new_wasm_hash` (admin only).
+    /// Storage is kept; call `migrate` afterwards if the new code bumps CONTRACT_VERSION.
+    /// Appends a `CodeRevision` with `notes_hash`, the hash of the release's migration
+    /// notes, to `get_version_history`.
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
+        notes_hash: BytesN<32>,
+    ) -> Result<(), RevoraError> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        Self::require_not_frozen(&env)?;
+        let mut history = Self::get_version_history(env.clone());
+        history.push_back(CodeRevision {
+            version: Self::get_version(env.clone()),
+            wasm_hash: new_wasm_hash.clone(),
+            upgraded_at: env.ledger().timestamp(),
+            notes_hash,
+        });
+        env.storage()
+            .persistent()
+            .set(&AdminKey::UpgradeHistory, &history);
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        env.events().publish((EVENT_UPGRADED, admin), new_wasm_hash);
+        Ok(())
+    }
+
+    /// Bring storage written by older code up to CONTRACT_VERSION (admin only). Runs once
+    /// per version: fails with `InvalidState` when storage is already current. Returns the
+    /// new version.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, RevoraError> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        let from = Self::get_version(env.clone());
+        if from >= CONTRACT_VERSION {
+            return Err(RevoraError::InvalidState);
+        }
+
+        // v1 -> v2: the single legacy safety address becomes the first `SafetySet` entry.
+        if !env.storage().persistent().has(&DataKey::SafetySet) {
+            let set = Self::get_safety_addresses(env.clone());
+            if !set.is_empty() {
+                env.storage().persistent().set(&DataKey::SafetySet, &set);
+            }
+        }
+        env.storage().persistent().remove(&DataKey::Safety);
+
+        // v2 -> v3: admin, pause and freeze flags move to instance storage.
+        for key in [DataKey::Admin, DataKey::Paused, DataKey::Frozen] {
+            if let Some(val) = env.storage().persistent().get::<DataKey, Val>(&key) {
+                if !env.storage().instance().has(&key) {
+                    env.storage().instance().set(&key, &val);
+                }
+                env.storage().persistent().remove(&key);
+            }
+        }
+        Self::extend_instance(&env);
+
+        env.storage()
+            .persistent()
+            .set(&AdminKey::ContractVersion, &CONTRACT_VERSION);
+        let mut history = Self::get_version_history(env.clone());
+        if let Some(mut latest) = history.pop_back() {
+            latest.version = CONTRACT_VERSION;
+            history.push_back(latest);
+            env.storage()
+                .persistent()
+                .set(&AdminKey::UpgradeHistory, &history);
+        }
+        env.events()
+            .publish((EVENT_MIGRATED, admin), (from, CONTRACT_VERSION));
+        Ok(CONTRACT_VERSION)
+    }
+
+    /// Recompute an offering's summed holder share bps from its holder registry (admin
+    /// only). Offerings whose shares were set before the total was tracked reject share
+    /// updates with `InvalidState` until this runs; it is safe to repeat. `LimitReached`
+    /// above 100 registered holders. Emits `migrated` and returns the total.
+    pub fn migrate_share_total(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<u32, RevoraError> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        let holder_count = Self::get_holder_count(env.clone(), token.clone());
+        if holder_count > MAX_BACKFILL_HOLDERS {
+            return Err(RevoraError::LimitReached);
+        }
+
+        let mut total: u32 = 0;
+        for i in 0..holder_count {
+            let holder: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::HolderAt(token.clone(), i))
+                .unwrap();
+            let share = Self::get_holder_share(env.clone(), token.clone(), holder);
+            total = total.checked_add(share).ok_or(RevoraError::InvalidState)?;
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalShareBps(token.clone()), &total);
+        env.events().publish((EVENT_MIGRATED, admin, token), total);
+        Ok(total)
+    }
+
+    /// Extend the TTL of an offering's core entries (registration, issuer lookup, payment
+    /// token, period and holder counters, blacklist) so they are not archived between
+    /// deposits. Anyone may call; `OfferingNotFound` if the offering does not exist.
+    pub fn bump_offering(env: Env, token: Address) -> Result<(), RevoraError> {
+        let issuer = Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        Self::extend_offering_ttl(&env, &issuer, &token);
+        Ok(())
+    }
+
+    /// Extend the TTL of `holder`'s share, claim cursor and payout settings for `token`.
+    /// Anyone may call; `OfferingNotFound` if the offering does not exist.
+    pub fn bump_holder(env: Env, token: Address, holder: Address) -> Result<(), RevoraError> {
+        if Self::get_current_issuer(&env, &token).is_none() {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        Self::extend_instance(&env);
+        Self::extend_holder_ttl(&env, &token, &holder);
+        Ok(())
+    }
+
+    /// Apply a partial config update (admin only). Fields left as `None` keep their current
+    /// (possibly default) value. Records the current config schema version.
+    pub fn reconfigure(env: Env, caller: Address, update: ConfigUpdate) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        Self::require_not_frozen(&env)?;
+        Self::validate_config_update(&env, &update)?;
+        if let Some(enabled) = update.event_versioning {
+            env.storage()
+                .persistent()
+                .set(&DataKey::EventVersioningEnabled, &enabled);
+        }
+        if let Some(enabled) = update.topics_hint {
+            env.storage()
+                .persistent()
+                .set(&DataKey::TopicsHintEnabled, &enabled);
+        }
+        if let Some(threshold) = update.strike_threshold {
+            env.storage()
+                .persistent()
+                .set(&DataKey::StrikeThreshold, &threshold);
+        }
+        if let Some(secs) = update.max_pause_secs {
+            env.storage()
+                .persistent()
+                .set(&DataKey::MaxPauseSecs, &secs);
+        }
+        if let Some(secs) = update.sweep_grace_secs {
+            env.storage()
+                .persistent()
+                .set(&DataKey::SweepGraceSecs, &secs);
+        }
+        if let Some(bps) = update.platform_fee_bps {
+            env.storage().persistent().set(&FeeKey::Bps, &bps);
+        }
+        if let Some(len) = update.max_metadata_len {
+            env.storage()
+                .persistent()
+                .set(&MetadataKey::MaxLength, &len);
+        }
+        if let Some(secs) = update.transfer_ttl_secs {
+            env.storage()
+                .persistent()
+                .set(&OfferingKey::TransferTtlSecs, &secs);
+        }
+        if let Some(enabled) = update.verify_offering_token {
+            if enabled {
+                env.storage()
+                    .persistent()
+                    .set(&OfferingKey::VerifyToken, &true);
+            } else {
+                env.storage().persistent().remove(&OfferingKey::VerifyToken);
+            }
+        }
+        if let Some(secs) = update.min_claim_delay_secs {
+            env.storage()
+                .persistent()
+                .set(&ClaimKey::MinDelaySecs, &secs);
+        }
+        if let Some(secs) = update.max_claim_delay_secs {
+            env.storage()
+                .persistent()
+                .set(&ClaimKey::MaxDelaySecs, &secs);
+        }
+        if let Some(periods) = update.delinquency_periods {
+            env.storage()
+                .persistent()
+                .set(&OfferingKey::DelinquencyPeriods, &periods);
+        }
+        if let Some(enabled) = update.reject_underpayment {
+            if enabled {
+                env.storage()
+                    .persistent()
+                    .set(&EscrowKey::RejectUnderpayment, &true);
+            } else {
+                env.storage()
+                    .persistent()
+                    .remove(&EscrowKey::RejectUnderpayment);
+            }
+        }
+        for (key, value) in [
+            (LimitKey::PageLimit, update.page_limit),
+            (LimitKey::ClaimPeriods, update.claim_periods),
+        ] {
+            match value {
+                Some(0) => env.storage().instance().remove(&key),
+                Some(n) => env.storage().instance().set(&key, &n),
+                None => {}
+            }
+        }
+        Self::extend_instance(&env);
+        match update.max_offerings_per_issuer {
+            Some(0) => env
+                .storage()
+                .persistent()
+                .remove(&OfferingKey::MaxPerIssuer),
+            Some(max) => env
+                .storage()
+                .persistent()
+                .set(&OfferingKey::MaxPerIssuer, &max),
+            None => {}
+        }
+        match update.max_total_offerings {
+            Some(0) => env.storage().persistent().remove(&OfferingKey::MaxTotal),
+            Some(max) => env.storage().persistent().set(&OfferingKey::MaxTotal, &max),
+            None => {}
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::ConfigVersion, &CONFIG_VERSION);
+        let config = Self::get_config(env.clone());
+        env.events().publish((EVENT_CONFIG_SET, caller), config);
+        Ok(())
+    }
+
+    /// Grant `role` to `addr` (admin only). Idempotent.
+    pub fn grant_role(
+        env: Env,
+        admin: Address,
+        role: Role,
+        addr: Address,
+    ) -> Result<(), RevoraError> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        Self::require_not_frozen(&env)?;
+        env.storage()
+            .persistent()
+            .set(&AdminKey::RoleMember(role, addr.clone()), &true);
+        env.events().publish((EVENT_ROLE_GRANTED, role), addr);
+        Ok(())
+    }
+
+    /// Revoke `role` from `addr` (admin only). Idempotent.
+    pub fn revoke_role(
+        env: Env,
+        admin: Address,
+        role: Role,
+        addr: Address,
+    ) -> Result<(), RevoraError> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        Self::require_not_frozen(&env)?;
+        env.storage()
+            .persistent()
+            .remove(&AdminKey::RoleMember(role, addr.clone()));
+        env.events().publish((EVENT_ROLE_REVOKED, role), addr);
+        Ok(())
+    }
+
+    /// Replace `addr`'s capability set with exactly `roles` (admin only), so each owner
+    /// can be given a distinct slice of duties in one call. Emits a grant or revoke event
+    /// for each role that changes; an empty list strips every role.
+    ///
+    /// Roles are capability sets only: there is no vote weight or approval threshold, since
+    /// the contract has a single admin rather than a multisig to weight. Every holder of a
+    /// role can use it alone.
+    pub fn set_roles(
+        env: Env,
+        admin: Address,
+        addr: Address,
+        roles: Vec<Role>,
+    ) -> Result<(), RevoraError> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        Self::require_not_frozen(&env)?;
+        for role in ALL_ROLES {
+            let key = AdminKey::RoleMember(role, addr.clone());
+            let held = env.storage().persistent().has(&key);
+            let wanted = roles.contains(role);
+            if wanted && !held {
+                env.storage().persistent().set(&key, &true);
+                env.events()
+                    .publish((EVENT_ROLE_GRANTED, role), addr.clone());
+            } else if held && !wanted {
+                env.storage().persistent().remove(&key);
+                env.events()
+                    .publish((EVENT_ROLE_REVOKED, role), addr.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a safety address (admin only). Any safety address may pause or unpause.
+    /// Idempotent; at most MAX_SAFETY_ADDRESSES.
+    pub fn add_safety(env: Env, caller: Address, safety: Address) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        let mut set = Self::get_safety_addresses(env.clone());
+        if set.contains(&safety) {
+            return Ok(());
+        }
+        if set.len() >= MAX_SAFETY_ADDRESSES {
+            return Err(RevoraError::LimitReached);
+        }
+        set.push_back(safety.clone());
+        env.storage().persistent().set(&DataKey::SafetySet, &set);
+        env.events().publish((EVENT_SAFETY_ADDED, caller), safety);
+        Ok(())
+    }
+
+    /// Remove a safety address (admin only). Idempotent.
+    pub fn remove_safety(env: Env, caller: Address, safety: Address) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        let mut set = Self::get_safety_addresses(env.clone());
+        let Some(idx) = set.first_index_of(&safety) else {
+            return Ok(());
+        };
+        set.remove(idx);
+        env.storage().persistent().set(&DataKey::SafetySet, &set);
+        env.events().publish((EVENT_SAFETY_REMOVED, caller), safety);
+        Ok(())
+    }
+
+    /// Pause the contract (admin only). Pausing again while paused renews the pause window.
+    pub fn pause_admin(env: Env, caller: Address) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        Self::write_instance(&env, &DataKey::Paused, &true);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PausedAt, &env.ledger().timestamp());
+        env.events().publish((EVENT_PAUSED, caller.clone()), ());
+        Ok(())
+    }
+
+    /// Unpause the contract (admin only). Idempotent.
+    pub fn unpause_admin(env: Env, caller: Address) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        Self::write_instance(&env, &DataKey::Paused, &false);
+        env.events().publish((EVENT_UNPAUSED, caller.clone()), ());
+        Ok(())
+    }
+
+    /// Pause the contract (safety addresses or `Pauser` role). Pausing again while paused
+    /// renews the pause window.
+    pub fn pause_safety(env: Env, caller: Address) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::require_safety(&env, &caller)?;
+        Self::write_instance(&env, &DataKey::Paused, &true);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PausedAt, &env.ledger().timestamp());
+        env.events().publish((EVENT_PAUSED, caller.clone()), ());
+        Ok(())
+    }
+
+    /// Unpause the contract (safety addresses or `Pauser` role). Idempotent.
+    pub fn unpause_safety(env: Env, caller: Address) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::require_safety(&env, &caller)?;
+        Self::write_instance(&env, &DataKey::Paused, &false);
+        env.events().publish((EVENT_UNPAUSED, caller.clone()), ());
+        Ok(())
+    }
+
+    /// Run the offering's internal consistency checks (holder shares and reported
+    /// concentration within 10_000 bps; `period_id` paid out no more than deposited, and
+    /// not below zero after sweeps). On a violation the offering is frozen: every call
+    /// gated like `pause_offering` fails with `OfferingFrozen` until the admin runs
+    /// `clear_invariant_freeze`. Permissionless, so monitors can call it. Returns the
+    /// violation found, if any.
+    pub fn check_invariants(
+        env: Env,
+        token: Address,
+        period_id: u64,
+    ) -> Result<Option<InvariantViolation>, RevoraError> {
+        let issuer = Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        let total_bps: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalShareBps(token.clone()))
+            .unwrap_or(0);
+        let concentration = Self::get_current_concentration(env.clone(), issuer, token.clone());
+        let violation = if total_bps > 10_000 || concentration.unwrap_or(0) > 10_000 {
+            Some(InvariantViolation::ConcentrationOverflow)
+        } else {
+            Self::period_violation(&env, &token, period_id)
+        };
+        if let Some(v) = violation {
+            Self::freeze_offering_on(&env, &token, v, Some(period_id));
+        }
+        Ok(violation)
+    }
+
+    /// Admin clears an invariant freeze after reviewing the offering.
+    pub fn clear_invariant_freeze(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<(), RevoraError> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        let key = OfferingKey::InvariantFrozen(token.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(RevoraError::InvalidState);
+        }
+        env.storage().persistent().remove(&key);
+        env.events()
+            .publish((EVENT_INVARIANT_CLEARED, token), admin);
+        Ok(())
+    }
+
+    /// Override the platform fee for one offering (admin or `FeeManager`), e.g. a negotiated discount
+    /// or premium. `None` removes the override so the global `platform_fee_bps` applies.
+    /// Fails with `InvalidAmount` above MAX_PLATFORM_FEE_BPS.
+    pub fn set_offering_fee_bps(
+        env: Env,
+        admin: Address,
+        token: Address,
+        fee_bps: Option<u32>,
+    ) -> Result<(), RevoraError> {
+        admin.require_auth();
+        Self::require_admin_or_role(&env, &admin, Role::FeeManager)?;
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+
+        let key = FeeKey::OfferingBps(token.clone());
+        match fee_bps {
+            Some(bps) if bps > MAX_PLATFORM_FEE_BPS => return Err(RevoraError::InvalidAmount),
+            Some(bps) => env.storage().persistent().set(&key, &bps),
+            None => env.storage().persistent().remove(&key),
+        }
+        env.events()
+            .publish((EVENT_OFFERING_FEE_SET, token), (admin, fee_bps));
+        Ok(())
+    }
+
+    /// Transfer all accrued platform fees in `payment_token` to `to` (admin or `FeeManager`).
+    /// Returns the amount withdrawn; 0 is a no-op.
+    pub fn withdraw_platform_fees(
+        env: Env,
+        admin: Address,
+        payment_token: Address,
+        to: Address,
+    ) -> Result<i128, RevoraError> {
+        admin.require_auth();
+        Self::require_admin_or_role(&env, &admin, Role::FeeManager)?;
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env)?;
+
+        let amount = Self::get_accrued_fees(env.clone(), payment_token.clone());
+        if amount == 0 {
+            return Ok(0);
+        }
+        env.storage()
+            .persistent()
+            .remove(&FeeKey::Accrued(payment_token.clone()));
+        token::Client::new(&env, &payment_token).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount,
+        );
+        env.events()
+            .publish((EVENT_FEE_WITHDRAWN, payment_token), (to, amount));
+        Ok(amount)
+    }
+
+    /// Set the broker who introduced the offering and their cut of the platform fee, in bps
+    /// of the fee (max 10000). Later deposits credit the referrer with
+    /// `fee * referral_bps / 10000`; the platform keeps the rest. `referral_bps` of 0 removes
+    /// the referrer. Fees accrued before the change are unaffected.
+    pub fn set_referrer(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        referrer: Address,
+        referral_bps: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        if referral_bps > BPS_DENOMINATOR as u32 {
+            return Err(RevoraError::InvalidShareBps);
+        }
+
+        let key = FeeKey::Referrer(token.clone());
+        if referral_bps == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage()
+                .persistent()
+                .set(&key, &(referrer.clone(), referral_bps));
+        }
+        env.events().publish(
+            (EVENT_REFERRER_SET, issuer, token),
+            (referrer, referral_bps),
+        );
+        Ok(())
+    }
+
+    /// Transfer all referral fees accrued to `referrer` in `payment_token` to the referrer.
+    /// Returns the amount claimed; 0 is a no-op.
+    pub fn claim_referral_fees(
+        env: Env,
+        referrer: Address,
+        payment_token: Address,
+    ) -> Result<i128, RevoraError> {
+        referrer.require_auth();
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env)?;
+
+        let amount = Self::get_referral_fees(env.clone(), referrer.clone(), payment_token.clone());
+        if amount == 0 {
+            return Ok(0);
+        }
+        env.storage().persistent().remove(&FeeKey::ReferralAccrued(
+            referrer.clone(),
+            payment_token.clone(),
+        ));
+        token::Client::new(&env, &payment_token).transfer(
+            &env.current_contract_address(),
+            &referrer,
+            &amount,
+        );
+        env.events()
+            .publish((EVENT_REFERRAL_CLAIMED, referrer), (payment_token, amount));
+        Ok(amount)
+    }
+
+    // ── Upgradeability guard and freeze (#32) ───────────────────
+
+    /// Set the admin address. May only be called once; caller must authorize as the new admin.
+    pub fn set_admin(env: Env, admin: Address) -> Result<(), RevoraError> {
+        admin.require_auth();
+        if Self::read_admin(&env).is_some() {
+            return Err(RevoraError::LimitReached);
+        }
+        Self::write_instance(&env, &DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Freeze the contract: no further state-changing operations allowed. Only admin may call.
+    /// Emits event. Claim and read-only functions remain allowed. Records the freeze time,
+    /// from which `emergency_withdraw` opens after EMERGENCY_WITHDRAW_DELAY_SECS.
+    pub fn freeze(env: Env) -> Result<(), RevoraError> {
+        let admin = Self::read_admin(&env).ok_or(RevoraError::LimitReached)?;
+        admin.require_auth();
+        if !Self::is_frozen(env.clone()) {
+            env.storage()
+                .instance()
+                .set(&ClaimKey::FrozenAt, &env.ledger().timestamp());
+        }
+        Self::write_instance(&env, &DataKey::Frozen, &true);
+        env.events().publish((EVENT_FREEZE, admin), true);
+        Ok(())
+    }
+
+    /// Record a strike for misbehavior detected off-chain (upheld dispute, late report).
+    /// Admin or `Reporter` only.
+    pub fn record_strike(
+        env: Env,
+        caller: Address,
+        issuer: Address,
+        reason: StrikeReason,
+    ) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::require_admin_or_role(&env, &caller, Role::Reporter)?;
+        Self::require_not_frozen(&env)?;
+        let strikes = Self::add_strike(&env, &issuer, reason);
+        env.events()
+            .publish((EVENT_STRIKE, issuer, caller), (reason, strikes));
+        Ok(())
+    }
+
+    /// Lift a reporting suspension after admin review. Resets the active strike count;
+    /// per-reason history is kept.
+    pub fn reinstate_issuer(env: Env, caller: Address, issuer: Address) -> Result<(), RevoraError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        Self::require_not_frozen(&env)?;
+        let key = DataKey::IssuerReputation(issuer.clone());
+        let mut rep = Self::get_issuer_reputation(env.clone(), issuer.clone());
+        rep.strikes = 0;
+        rep.suspended = false;
+        env.storage().persistent().set(&key, &rep);
+        env.events()
+            .publish((EVENT_ISSUER_REINSTATED, issuer), caller);
+        Ok(())
+    }
+
+    // ── Testnet mode configuration (#24) ───────────────────────
+
+    /// Enable or disable testnet mode. Only admin may call.
+    /// When enabled, certain validations are relaxed for testnet deployments.
+    /// Emits event with new mode state.
+    pub fn set_testnet_mode(env: Env, enabled: bool) -> Result<(), RevoraError> {
+        let admin = Self::read_admin(&env).ok_or(RevoraError::LimitReached)?;
+        admin.require_auth();
+        let mode_key = DataKey::TestnetMode;
+        env.storage().persistent().set(&mode_key, &enabled);
+        env.events().publish((EVENT_TESTNET_MODE, admin), enabled);
+        Ok(())
+    }
+
+    /// Testnet only: wipe an offering's periods, holder shares, claim progress and receipts,
+    /// report history, report attestations, audit counters and any invariant freeze so a
+    /// shared test deployment can be reused. The offering registration, its payout asset
+    /// and any escrowed tokens are left as they are. Admin only; `InvalidState` outside
+    /// testnet mode or when a period was distributed by Merkle root or snapshot,
+    /// `LimitReached` if the offering has more than MAX_RESET_ENTRIES periods or holders,
+    /// or a holder more than MAX_RESET_ENTRIES claim receipts.
+    pub fn reset_offering(env: Env, admin: Address, token: Address) -> Result<(), RevoraError> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        if !Self::is_testnet_mode(env.clone()) {
+            return Err(RevoraError::InvalidState);
+        }
+        let issuer = Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+
+        let store = env.storage().persistent();
+        let period_count: u32 = store.get(&DataKey::PeriodCount(token.clone())).unwrap_or(0);
+        let holder_count = Self::get_holder_count(env.clone(), token.clone());
+        if period_count > MAX_RESET_ENTRIES || holder_count > MAX_RESET_ENTRIES {
+            return Err(RevoraError::LimitReached);
+        }
+
+        let mut holders: Vec<Address> = Vec::new(&env);
+        for i in 0..holder_count {
+            let key = DataKey::HolderAt(token.clone(), i);
+            let holder: Address = store.get(&key).unwrap();
+            store.remove(&key);
+            store.remove(&DataKey::HolderShare(token.clone(), holder.clone()));
+            store.remove(&DataKey::LastClaimedIdx(token.clone(), holder.clone()));
+            store.remove(&ClaimKey::SettledBand(token.clone(), holder.clone()));
+            store.remove(&ClaimKey::EmergencyWithdrawn(token.clone(), holder.clone()));
+            let history_key = ClaimKey::HistoryCount(token.clone(), holder.clone());
+            let history: u32 = store.get(&history_key).unwrap_or(0);
+            if history > MAX_RESET_ENTRIES {
+                return Err(RevoraError::LimitReached);
+            }
+            for seq in 0..history {
+                store.remove(&ClaimKey::History(token.clone(), holder.clone(), seq));
+            }
+            store.remove(&history_key);
+            holders.push_back(holder);
+        }
+
+        for i in 0..period_count {
+            let entry_key = DataKey::PeriodEntry(token.clone(), i);
+            let period_id: u64 = store.get(&entry_key).unwrap();
+            // Proof and snapshot claimants are not enumerable, so their claim markers
+            // could not be cleared.
+            if store.has(&MerkleKey::Root(token.clone(), period_id))
+                || store.has(&SnapshotKey::ForPeriod(token.clone(), period_id))
+            {
+                return Err(RevoraError::InvalidState);
+            }
+            store.remove(&entry_key);
+            store.remove(&DataKey::PeriodRevenue(token.clone(), period_id));
+            store.remove(&DataKey::PeriodDepositTime(token.clone(), period_id));
+            store.remove(&DataKey::PeriodIndex(token.clone(), period_id));
+            store.remove(&DataKey::PeriodPaused(token.clone(), period_id));
+            store.remove(&SnapshotKey::PeriodSupply(token.clone(), period_id));
+            store.remove(&EscrowKey::Distributed(token.clone(), period_id));
+            store.remove(&EscrowKey::Swept(token.clone(), period_id));
+            store.remove(&EscrowKey::Fee(token.clone(), period_id));
+            store.remove(&EscrowKey::Reported(token.clone(), period_id));
+            for holder in holders.iter() {
+                store.remove(&DataKey::PushPaid(token.clone(), period_id, holder));
+            }
+        }
+
+        let reports_key = DataKey::RevenueReports(issuer.clone(), token.clone());
+        let reports: Map<u64, (i128, u64)> =
+            store.get(&reports_key).unwrap_or_else(|| Map::new(&env));
+        let report_count = reports.len();
+        for period_id in reports.keys().iter() {
+            store.remove(&AuditKey::Attestations(token.clone(), period_id));
+        }
+        store.remove(&reports_key);
+        store.remove(&DataKey::AuditSummary(issuer, token.clone()));
+        store.remove(&DataKey::PeriodCount(token.clone()));
+        store.remove(&ClaimKey::ClaimWatermark(token.clone()));
+        store.remove(&DataKey::HolderCount(token.clone()));
+        store.remove(&DataKey::TotalShareBps(token.clone()));
+        store.remove(&OfferingKey::Closed(token.clone()));
+        store.remove(&OfferingKey::Paused(token.clone()));
+        store.remove(&OfferingKey::InvariantFrozen(token.clone()));
+
+        Self::update_metrics(&env, |m| {
+            m.periods = m.periods.saturating_sub(period_count);
+            m.holder_shares = m.holder_shares.saturating_sub(holder_count);
+            m.reports = m.reports.saturating_sub(report_count);
+        });
+        env.events().publish(
+            (EVENT_OFFERING_RESET, admin, token),
+            (period_count, holder_count),
+        );
+        Ok(())
+    }
+}
@@ -1,45 +1,22 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Map,
-    Symbol, Vec,
+    contract, contractimpl, contracttype, symbol_short, token, xdr::ToXdr, Address, Bytes, BytesN,
+    Env, IntoVal, Map, String, Symbol, Val, Vec,
 };
 
-/// Centralized contract error codes. Auth failures are signaled by host panic (require_auth).
-#[contracterror]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
-#[repr(u32)]
-pub enum RevoraError {
-    /// revenue_share_bps exceeded 10000 (100%).
-    InvalidRevenueShareBps = 1,
-    /// Reserved for future use (e.g. offering limit per issuer).
-    LimitReached = 2,
-    /// Holder concentration exceeds configured limit and enforcement is enabled.
-    ConcentrationLimitExceeded = 3,
-    /// No offering found for the given (issuer, token) pair.
-    OfferingNotFound = 4,
-    /// Revenue already deposited for this period.
-    PeriodAlreadyDeposited = 5,
-    /// No unclaimed periods for this holder.
-    NoPendingClaims = 6,
-    /// Holder is blacklisted for this offering.
-    HolderBlacklisted = 7,
-    /// Holder share_bps exceeded 10000 (100%).
-    InvalidShareBps = 8,
-    /// Payment token does not match previously set token for this offering.
-    PaymentTokenMismatch = 9,
-    /// Contract is frozen; state-changing operations are disabled.
-    ContractFrozen = 10,
-    /// Revenue for this period is not yet claimable (delay not elapsed).
-    ClaimDelayNotElapsed = 11,
-    /// A transfer is already pending for this offering.
-    IssuerTransferPending = 12,
-    /// No transfer is pending for this offering.
-    NoTransferPending = 13,
-    /// Caller is not authorized to accept this transfer.
-    UnauthorizedTransferAccept = 14,
-    /// Payout asset does not match the configured payout asset for this offering.
-    PayoutAssetMismatch = 15,
-}
+mod claims;
+mod compliance;
+mod errors;
+mod governance;
+pub mod math;
+mod offerings;
+mod queries;
+mod revenue;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+pub use errors::RevoraError;
+pub use math::RoundingMode;
+use math::BPS_DENOMINATOR;
 
 // ── Event symbols ────────────────────────────────────────────
 const EVENT_REVENUE_REPORTED: Symbol = symbol_short!("rev_rep");
@@ -59,25 +36,127 @@ const EVENT_REV_INIT_V1: Symbol = symbol_short!("rv_init1");
 const EVENT_REV_INIA_V1: Symbol = symbol_short!("rv_inia1");
 const EVENT_REV_REP_V1: Symbol = symbol_short!("rv_rep1");
 const EVENT_REV_REPA_V1: Symbol = symbol_short!("rv_repa1");
+// Versioned event symbols (v2): payloads are named `#[contracttype]` structs, so fields
+// can be added without breaking positional decoding.
+const EVENT_REV_REP_V2: Symbol = symbol_short!("rv_rep2");
+const EVENT_REV_DEPOSIT_V2: Symbol = symbol_short!("rv_dep2");
+const EVENT_CLAIM_V2: Symbol = symbol_short!("claim2");
 
 const EVENT_SCHEMA_VERSION: u32 = 1;
 const EVENT_CONCENTRATION_WARNING: Symbol = symbol_short!("conc_warn");
 const EVENT_REV_DEPOSIT: Symbol = symbol_short!("rev_dep");
+const EVENT_REV_SHORT: Symbol = symbol_short!("rev_short");
 const EVENT_CLAIM: Symbol = symbol_short!("claim");
 const EVENT_SHARE_SET: Symbol = symbol_short!("share_set");
 const EVENT_FREEZE: Symbol = symbol_short!("freeze");
+const EVENT_EMERGENCY_WITHDRAW: Symbol = symbol_short!("emerg_wd");
 const EVENT_CLAIM_DELAY_SET: Symbol = symbol_short!("delay_set");
 const EVENT_ISSUER_TRANSFER_PROPOSED: Symbol = symbol_short!("iss_prop");
 const EVENT_ISSUER_TRANSFER_ACCEPTED: Symbol = symbol_short!("iss_acc");
 const EVENT_ISSUER_TRANSFER_CANCELLED: Symbol = symbol_short!("iss_canc");
 const EVENT_TESTNET_MODE: Symbol = symbol_short!("test_mode");
 const EVENT_INIT: Symbol = symbol_short!("init");
+const EVENT_INIT_V2: Symbol = symbol_short!("init_v2");
+const EVENT_INIT_V3: Symbol = symbol_short!("init_v3");
+const EVENT_FEE_ACCRUED: Symbol = symbol_short!("fee_acc");
+const EVENT_FEE_WITHDRAWN: Symbol = symbol_short!("fee_wd");
+const EVENT_OFFERING_FEE_SET: Symbol = symbol_short!("fee_set");
+const EVENT_REFERRER_SET: Symbol = symbol_short!("ref_set");
+const EVENT_REFERRAL_ACCRUED: Symbol = symbol_short!("ref_acc");
+const EVENT_REFERRAL_CLAIMED: Symbol = symbol_short!("ref_claim");
+const EVENT_METADATA_SET: Symbol = symbol_short!("meta_set");
+const EVENT_METADATA_LOCALE_SET: Symbol = symbol_short!("meta_loc");
+const EVENT_METADATA_V2_SET: Symbol = symbol_short!("meta_v2");
+const EVENT_COMPLIANCE_OFFICER_SET: Symbol = symbol_short!("co_set");
+const EVENT_BLACKLIST_LOCKED: Symbol = symbol_short!("bl_lock");
+const EVENT_WL_ADD: Symbol = symbol_short!("wl_add");
+const EVENT_WL_REM: Symbol = symbol_short!("wl_rem");
+const EVENT_WL_ENABLED: Symbol = symbol_short!("wl_enable");
+const EVENT_GLOBAL_BL_ADD: Symbol = symbol_short!("gbl_add");
+const EVENT_GLOBAL_BL_REM: Symbol = symbol_short!("gbl_rem");
+const EVENT_HOLDER_LOCKUP: Symbol = symbol_short!("lockup");
+const EVENT_KYC_REGISTRY: Symbol = symbol_short!("kyc_reg");
+const EVENT_KYC_REQUIRED: Symbol = symbol_short!("kyc_req");
+const EVENT_RESTRICTIONS_SET: Symbol = symbol_short!("rstr_set");
+const EVENT_PROFILE_SET: Symbol = symbol_short!("prof_set");
+const EVENT_TERMS_SET: Symbol = symbol_short!("terms_set");
+const EVENT_TERMS_ACK: Symbol = symbol_short!("terms_ack");
+const EVENT_SCHEDULE_SET: Symbol = symbol_short!("sched_set");
+const EVENT_CURRENCY_SET: Symbol = symbol_short!("ccy_set");
+const EVENT_AUDITOR_ADDED: Symbol = symbol_short!("aud_add");
+const EVENT_AUDITOR_REMOVED: Symbol = symbol_short!("aud_rem");
+const EVENT_REPORT_ATTESTED: Symbol = symbol_short!("attest");
+const EVENT_DELINQUENT: Symbol = symbol_short!("delinq");
+const EVENT_DELINQUENCY_CLEARED: Symbol = symbol_short!("delinq_ok");
+const EVENT_BL_ADD_BULK: Symbol = symbol_short!("bl_addb");
+const EVENT_BL_REM_BULK: Symbol = symbol_short!("bl_remb");
+const EVENT_WL_ADD_BULK: Symbol = symbol_short!("wl_addb");
+const EVENT_WL_REM_BULK: Symbol = symbol_short!("wl_remb");
+const EVENT_ROLE_GRANTED: Symbol = symbol_short!("role_grnt");
+const EVENT_ROLE_REVOKED: Symbol = symbol_short!("role_rev");
+const EVENT_UPGRADED: Symbol = symbol_short!("upgraded");
+const EVENT_MIGRATED: Symbol = symbol_short!("migrated");
+const EVENT_SNAPSHOT_REUSED: Symbol = symbol_short!("snap_reus");
+const EVENT_CONFIG_SET: Symbol = symbol_short!("cfg_set");
+const EVENT_TOPICS_HINT: Symbol = symbol_short!("tpc_hint");
+const EVENT_STRIKE: Symbol = symbol_short!("strike");
+const EVENT_ISSUER_SUSPENDED: Symbol = symbol_short!("iss_susp");
+const EVENT_ISSUER_REINSTATED: Symbol = symbol_short!("iss_rein");
+const EVENT_RAISE_OPENED: Symbol = symbol_short!("rais_open");
+const EVENT_RAISE_SUBSCRIBED: Symbol = symbol_short!("rais_sub");
+const EVENT_RAISE_FINALIZED: Symbol = symbol_short!("rais_fin");
+const EVENT_RAISE_SETTLED: Symbol = symbol_short!("rais_set");
+const EVENT_SHARE_XFER_CFG: Symbol = symbol_short!("sx_cfg");
+const EVENT_SHARE_XFER_PROPOSED: Symbol = symbol_short!("sx_prop");
+const EVENT_SHARE_XFER_MATCHED: Symbol = symbol_short!("sx_rofr");
+const EVENT_SHARE_XFER_COMPLETED: Symbol = symbol_short!("sx_done");
+const EVENT_SHARE_XFER_CANCELLED: Symbol = symbol_short!("sx_canc");
+const EVENT_LISTING_CREATED: Symbol = symbol_short!("lst_new");
+const EVENT_LISTING_FILLED: Symbol = symbol_short!("lst_fill");
+const EVENT_LISTING_CANCELLED: Symbol = symbol_short!("lst_canc");
+const EVENT_PERIOD_PAUSED: Symbol = symbol_short!("per_pause");
+const EVENT_PERIOD_UNPAUSED: Symbol = symbol_short!("per_unpau");
+const EVENT_ROOT_SET: Symbol = symbol_short!("root_set");
+const EVENT_PROOF_CLAIM: Symbol = symbol_short!("prf_claim");
+const EVENT_SNAPSHOT_BALANCES: Symbol = symbol_short!("snap_bal");
+const EVENT_SNAPSHOT_SET: Symbol = symbol_short!("snap_set");
+const EVENT_SNAPSHOT_CLAIM: Symbol = symbol_short!("snap_clm");
+const EVENT_BALANCE_MODE: Symbol = symbol_short!("bal_mode");
+const EVENT_STREAM_SET: Symbol = symbol_short!("strm_set");
+const EVENT_CLAIM_ORDER: Symbol = symbol_short!("clm_ord");
+const EVENT_CLAIM_ORDER_PREF: Symbol = symbol_short!("clm_pref");
+const EVENT_REINVEST_RATE_SET: Symbol = symbol_short!("rinv_rate");
+const EVENT_REINVEST_SET: Symbol = symbol_short!("rinv_set");
+const EVENT_REINVESTED: Symbol = symbol_short!("reinvest");
+const EVENT_DISTRIBUTE_BATCH: Symbol = symbol_short!("dist_bat");
+const EVENT_SAFETY_ADDED: Symbol = symbol_short!("safe_add");
+const EVENT_SAFETY_REMOVED: Symbol = symbol_short!("safe_rem");
+const EVENT_RESIDUAL_SWEPT: Symbol = symbol_short!("sweep");
+const EVENT_DELEGATE_SET: Symbol = symbol_short!("dlg_set");
+const EVENT_DELEGATE_REVOKED: Symbol = symbol_short!("dlg_rev");
+const EVENT_DELEGATED_CLAIM: Symbol = symbol_short!("dlg_claim");
+const EVENT_PAYOUT_ADDRESS_SET: Symbol = symbol_short!("pay_addr");
+const EVENT_OFFERING_RESET: Symbol = symbol_short!("off_reset");
+const EVENT_CLAIM_EXPIRY_SET: Symbol = symbol_short!("exp_set");
+const EVENT_RECLAIM_TREASURY_SET: Symbol = symbol_short!("treas_set");
+const EVENT_EXPIRED_RECLAIMED: Symbol = symbol_short!("reclaim");
+const EVENT_OFFERING_CLOSED: Symbol = symbol_short!("off_close");
+const EVENT_OFFERING_PAUSED: Symbol = symbol_short!("off_pause");
+const EVENT_OFFERING_UNPAUSED: Symbol = symbol_short!("off_unpau");
+const EVENT_DONATION_SET: Symbol = symbol_short!("don_set");
+const EVENT_DONATED: Symbol = symbol_short!("donated");
+const EVENT_SUB_ACCOUNTS_SET: Symbol = symbol_short!("sub_set");
+const EVENT_SUB_ALLOCATED: Symbol = symbol_short!("sub_alloc");
+const EVENT_BONUS_SET: Symbol = symbol_short!("bonus_set");
+const EVENT_BONUS_FUNDED: Symbol = symbol_short!("bonus_fnd");
+const EVENT_BONUS_WITHDRAWN: Symbol = symbol_short!("bonus_wd");
+const EVENT_BONUS_PAID: Symbol = symbol_short!("bonus_pd");
+const EVENT_INVARIANT_FROZEN: Symbol = symbol_short!("inv_frz");
+const EVENT_INVARIANT_CLEARED: Symbol = symbol_short!("inv_clr");
 const EVENT_PAUSED: Symbol = symbol_short!("paused");
 const EVENT_UNPAUSED: Symbol = symbol_short!("unpaused");
 const EVENT_DIST_CALC: Symbol = symbol_short!("dist_calc");
 
-const BPS_DENOMINATOR: i128 = 10_000;
-
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub struct Offering {
@@ -87,6 +166,104 @@ pub struct Offering {
     pub payout_asset: Address,
 }
 
+/// Investor restrictions an offering enforces on share assignment and claims.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Restrictions {
+    /// Jurisdiction codes holders must be in (e.g. `US`, `DE`); empty allows any.
+    pub allowed_jurisdictions: Vec<Symbol>,
+    /// Only accredited holders qualify.
+    pub accredited_only: bool,
+}
+
+/// Compliance profile of a holder on one offering, set by its compliance managers.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct HolderProfile {
+    pub jurisdiction: Symbol,
+    pub accredited: bool,
+}
+
+/// Hash of an offering's terms document, anchored by its issuer.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfferingTerms {
+    pub hash: BytesN<32>,
+    /// Holders must acknowledge this hash before being given a share.
+    pub required: bool,
+    pub set_at: u64,
+}
+
+/// A holder's acceptance of one terms version.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TermsAcknowledgement {
+    pub hash: BytesN<32>,
+    pub acknowledged_at: u64,
+}
+
+/// Recurring period calendar for an offering: period 1 starts at `start`, and each
+/// period lasts `interval_secs`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodSchedule {
+    pub start: u64,
+    pub interval_secs: u64,
+}
+
+/// Delinquency recorded by `flag_delinquent`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delinquency {
+    /// Scheduled periods that had ended without a deposit when flagged.
+    pub missed: u32,
+    /// Ledger timestamp of the flag.
+    pub flagged_at: u64,
+}
+
+/// Result of `register_offering`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegisteredOffering {
+    /// Platform-wide offering ID, starting at 1. A re-registered token keeps its first ID.
+    pub id: u64,
+    /// Position in the issuer's offering list (`get_offerings_page`).
+    pub index: u32,
+    /// The offering as stored.
+    pub offering: Offering,
+}
+
+/// One offering in `export_offerings_page`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportedOffering {
+    pub id: u64,
+    /// The offering under its current issuer.
+    pub offering: Offering,
+    pub period_count: u32,
+    pub holder_count: u32,
+}
+
+/// One holder in `export_holders_page`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportedHolder {
+    pub holder: Address,
+    pub share_bps: u32,
+    /// Index of the next deposited period the holder has yet to claim.
+    pub next_claim_idx: u32,
+}
+
+/// One deposited period in `export_periods_page`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportedPeriod {
+    pub period_id: u64,
+    pub revenue: i128,
+    /// Ledger timestamp of the deposit.
+    pub deposited_at: u64,
+}
+
 /// Per-offering concentration guardrail config (#26).
 /// max_bps: max allowed single-holder share in basis points (0 = disabled).
 /// enforce: if true, report_revenue fails when current concentration > max_bps.
@@ -105,6 +282,58 @@ pub struct AuditSummary {
     pub report_count: u64,
 }
 
+/// `rv_rep2` event payload, published with topics `(rv_rep2, issuer, token)`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RevenueReportedV2 {
+    pub payout_asset: Address,
+    pub period_id: u64,
+    pub amount: i128,
+    /// Amount reported for the period before this report, if any.
+    pub previous_amount: Option<i128>,
+    /// False when the report repeated a period without `override_existing` and was rejected.
+    pub accepted: bool,
+    pub blacklist: Vec<Address>,
+    /// The offering's accounting currency (`set_accounting_currency`), if tagged.
+    pub currency: Option<Symbol>,
+}
+
+/// `rv_dep2` event payload, published with topics `(rv_dep2, issuer, token)`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DepositedV2 {
+    pub payment_token: Address,
+    pub period_id: u64,
+    pub amount: i128,
+    pub deposited_at: u64,
+    /// The offering's accounting currency (`set_accounting_currency`), if tagged.
+    pub currency: Option<Symbol>,
+}
+
+/// `claim2` event payload, published with topics `(claim2, holder, token)`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimedV2 {
+    /// Total paid, including `bonus` and `donated`.
+    pub amount: i128,
+    pub bonus: i128,
+    pub donated: i128,
+    pub periods: Vec<u64>,
+    pub share_bps: u32,
+}
+
+/// An auditor's sign-off on a revenue report, recorded by `attest_report`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attestation {
+    pub auditor: Address,
+    /// Hash of the report document the auditor reviewed.
+    pub report_hash: BytesN<32>,
+    /// Reported amount at attestation time; differs from the report after an override.
+    pub amount: i128,
+    pub attested_at: u64,
+}
+
 /// Result of simulate_distribution (#29): per-holder payout and total.
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -115,20 +344,494 @@ pub struct SimulateDistributionResult {
     pub payouts: Vec<(Address, i128)>,
 }
 
-/// Rounding mode for distribution share calculations (#44).
+/// Deposit metadata for one period, from `get_period_info`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodInfo {
+    /// Amount deposited.
+    pub amount: i128,
+    /// Ledger timestamp of the deposit.
+    pub deposit_time: u64,
+    /// Merkle distribution root the period pays against, if any (32 bytes).
+    pub snapshot_ref: Option<Bytes>,
+    /// True once the residual was swept; the period pays nothing further.
+    pub finalized: bool,
+    /// Amount paid out so far by claims, pushes and proofs.
+    pub claimed_total: i128,
+}
+
+/// Order in which claims settle an offering's pending periods when a claim cannot take
+/// them all.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClaimOrder {
+    /// Oldest period first (default).
+    OldestFirst = 0,
+    /// Most recent period first.
+    NewestFirst = 1,
+    /// Each holder picks with `set_claim_order_preference`; oldest first until they do.
+    HolderChoice = 2,
+}
+
+/// Outcome of `claim_v2`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimResult {
+    /// Amount paid out.
+    pub amount: i128,
+    /// Periods settled by this call.
+    pub periods_claimed: u32,
+    /// True if deposited periods remain unclaimed after this call.
+    pub more_pending: bool,
+}
+
+/// Receipt written by every successful claim, from `get_claim_history_page`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimRecord {
+    /// Period IDs settled by the claim.
+    pub periods: Vec<u64>,
+    /// Amount paid for each entry of `periods`, before any bonus.
+    pub payouts: Vec<i128>,
+    /// Amount paid out.
+    pub amount: i128,
+    /// Ledger timestamp of the claim.
+    pub timestamp: u64,
+    /// Portion of `amount` sent to the holder's donation recipient (`set_donation`).
+    pub donated: i128,
+    /// Prompt-claim bonus included in `amount`.
+    pub bonus: i128,
+    /// Holder share in bps the claim was computed with.
+    pub share_bps: u32,
+    /// Offering-token balance used for balance-mode periods (`set_balance_mode`).
+    pub balance: Option<i128>,
+}
+
+/// Share of every claim a holder gives away, from `get_donation`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DonationSplit {
+    /// Receives the donated portion directly.
+    pub recipient: Address,
+    /// Portion of each claim donated, in basis points.
+    pub bps: u32,
+}
+
+/// End-client account inside a custodian's share, from `get_sub_accounts`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubAccount {
+    /// Custodian-assigned client identifier.
+    pub id: u64,
+    /// Portion of the custodian's share attributed to this client, in basis points.
+    pub bps: u32,
+}
+
+/// Escrow accounting for one period, from `get_period_escrow`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodEscrow {
+    /// Amount deposited for the period.
+    pub deposited: i128,
+    /// Amount paid out to holders so far.
+    pub distributed: i128,
+    /// Amount swept back to the issuer (0 until swept).
+    pub swept: i128,
+    /// True once `sweep_residual` has run; the period then pays nothing further.
+    pub closed: bool,
+}
+
+/// Prompt-claim reward for an offering, from `get_claim_bonus`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimBonus {
+    /// Bonus in basis points of the payout, paid from the offering's bonus pool.
+    pub bonus_bps: u32,
+    /// Periods claimed within this many seconds of their deposit earn the bonus.
+    pub window_secs: u64,
+}
+
+/// Reconciliation view of one period, from `get_period_accounting`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodAccounting {
+    /// Gross amount the issuer deposited, before the platform fee.
+    pub deposited: i128,
+    /// Platform fee (including any referral cut) withheld at deposit.
+    pub fee: i128,
+    /// Amount paid out to holders so far by claims, pushes and proofs.
+    pub claimed: i128,
+    /// Amount swept or reclaimed back to the issuer (0 until closed).
+    pub swept: i128,
+    /// Amount still held for the period: `deposited - fee - claimed - swept`.
+    pub residual: i128,
+    /// Revenue the issuer had reported for the period when it was deposited, if any.
+    pub reported: Option<i128>,
+}
+
+/// Resource estimate for a `claim` call, from `estimate_claim_cost`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimCostEstimate {
+    /// Periods the claim would settle.
+    pub periods: u32,
+    /// Contract storage entries read (excluding the payout token transfer).
+    pub storage_reads: u32,
+    /// Contract storage entries written.
+    pub storage_writes: u32,
+    /// Token transfers performed (0 or 1).
+    pub transfers: u32,
+}
+
+/// Network the contract is deployed on, chosen by `initialize_v3`. Selects the built-in
+/// limits returned by `get_network_limits`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetworkProfile {
+    Testnet = 0,
+    Futurenet = 1,
+    /// Conservative limits that leave headroom under mainnet resource caps.
+    Mainnet = 2,
+}
+
+/// Per-call limits in effect for the contract's network profile.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkLimits {
+    /// Maximum entries returned by one page of a paginated view.
+    pub page_limit: u32,
+    /// Maximum periods processed by one claim.
+    pub claim_periods: u32,
+    /// Maximum recipients paid by one `distribute_batch` call.
+    pub distribute_batch: u32,
+    /// Maximum entries accepted by one `set_holder_shares_bulk` call.
+    pub bulk_shares: u32,
+}
+
+/// Approximate persistent entry counts per subsystem, for monitoring ledger-rent exposure.
+/// Counters only grow on entry creation (and shrink on blacklist removal); they are not
+/// recomputed from storage.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StorageMetrics {
+    pub offerings: u32,
+    pub periods: u32,
+    pub holder_shares: u32,
+    pub blacklist_entries: u32,
+    pub reports: u32,
+}
+
+/// Kind of reporter misbehavior recorded against an issuer.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StrikeReason {
+    /// `report_revenue` for an already-reported period without `override_existing`.
+    RejectedOverride = 0,
+    /// A dispute against the issuer's report was upheld (recorded by admin).
+    UpheldDispute = 1,
+    /// Report submitted after its expected deadline (recorded by admin).
+    LateReport = 2,
+}
+
+/// Per-issuer misbehavior counters returned by `get_issuer_reputation`.
+/// `strikes` counts toward the suspension threshold and resets on reinstatement;
+/// the per-reason counters are cumulative history.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IssuerReputation {
+    pub strikes: u32,
+    pub rejected_overrides: u32,
+    pub upheld_disputes: u32,
+    pub late_reports: u32,
+    pub suspended: bool,
+}
+
+/// Lifecycle status of an offering, returned by `get_offering_status`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OfferingStatus {
+    /// Accepting deposits, reports and share changes.
+    Active = 0,
+    /// Closed by the issuer; only outstanding claims are paid.
+    Closed = 1,
+    /// The whole contract is frozen.
+    Frozen = 2,
+    /// Paused on its own (`pause_offering`) or by a global pause.
+    Paused = 3,
+}
+
+/// Internal consistency check that failed and froze an offering (`check_invariants`).
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// A period paid out more than was deposited for it.
+    ClaimedExceedsDeposit = 0,
+    /// Holder shares or reported concentration above 10_000 bps.
+    ConcentrationOverflow = 1,
+    /// Deposited revenue minus payouts and sweeps went below zero.
+    NegativeLiability = 2,
+}
+
+/// Contract-wide roles granted by the admin. Each role opens a narrow set of entrypoints
+/// alongside the admin (or safety/issuer) checks that already guard them.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// `set_offering_fee_bps`, `withdraw_platform_fees`.
+    FeeManager = 0,
+    /// Blacklist edits on every offering, including those with a compliance officer, and
+    /// the global blacklist.
+    ComplianceOfficer = 1,
+    /// `pause_safety` / `unpause_safety`, `pause_offering` / `unpause_offering`.
+    Pauser = 2,
+    /// `record_strike`.
+    Reporter = 3,
+}
+
+/// Every `Role`, in code order.
+const ALL_ROLES: [Role; 4] = [
+    Role::FeeManager,
+    Role::ComplianceOfficer,
+    Role::Pauser,
+    Role::Reporter,
+];
+
+/// Offering detail view returned by `get_offering_details`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfferingDetails {
+    pub offering: Offering,
+    pub status: OfferingStatus,
+    /// Compliance officer managing the blacklist, if any.
+    pub compliance_officer: Option<Address>,
+    /// True once the issuer permanently disabled blacklist additions.
+    pub blacklist_locked: bool,
+    /// Denomination of the offering's reported amounts (`set_accounting_currency`).
+    pub accounting_currency: Option<Symbol>,
+}
+
+/// Lifecycle of an offering's capital raise.
 #[contracttype]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum RoundingMode {
-    /// Truncate toward zero: share = (amount * bps) / 10000
-    Truncation = 0,
-    /// Round half up: share = (amount * bps * 2 + 10000) / 20000
-    RoundHalfUp = 1,
+pub enum RaiseStatus {
+    /// Accepting subscriptions until `deadline` or until `target` is reached.
+    Open = 0,
+    /// `min_raise` was met; funds went to the issuer and subscribers receive shares.
+    Succeeded = 1,
+    /// Undersubscribed; subscribers are refunded.
+    Failed = 2,
+}
+
+/// Capital raise for an offering. Subscribers pay the offering's payout asset and receive
+/// `contribution * allocation_bps / target` holder share bps on success.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapitalRaise {
+    /// Hard cap on total subscriptions.
+    pub target: i128,
+    /// Soft cap; below this at finalization the raise fails and subscribers are refunded.
+    pub min_raise: i128,
+    /// Holder share bps sold if the raise is fully subscribed.
+    pub allocation_bps: u32,
+    /// Ledger timestamp after which subscriptions close.
+    pub deadline: u64,
+    pub total_raised: i128,
+    pub status: RaiseStatus,
+}
+
+/// Per-offering secondary transfer policy.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShareTransferConfig {
+    /// Holders may move shares to other addresses.
+    pub transferable: bool,
+    /// Right-of-first-refusal window in seconds (0 = transfers complete immediately).
+    pub rofr_window_secs: u64,
+}
+
+/// A proposed holder-to-holder share transfer awaiting the ROFR window.
+/// `price` (in the offering's payout asset) is escrowed by the current `buyer`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShareTransfer {
+    pub seller: Address,
+    pub buyer: Address,
+    pub share_bps: u32,
+    pub price: i128,
+    /// Ledger timestamp when the ROFR window closes.
+    pub rofr_deadline: u64,
+    /// True once the issuer or another holder has matched the offer.
+    pub matched: bool,
+    pub completed: bool,
+    /// True once `cancel_share_transfer` has refunded the buyer.
+    pub cancelled: bool,
+}
+
+/// Seller's standing offer to sell `share_bps` for `price` in the offering's payout asset.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShareListing {
+    pub seller: Address,
+    pub share_bps: u32,
+    pub price: i128,
+    /// False once filled or cancelled.
+    pub active: bool,
+}
+
+/// Contract-wide settings view returned by `get_config`.
+/// Each field is stored under its own key and falls back to a default when absent, so an
+/// upgrade can introduce new fields without migrating existing storage.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractConfig {
+    /// Config schema version that last wrote settings (0 = never written, all defaults).
+    pub version: u32,
+    /// Emit versioned (v1 and v2) events alongside legacy events. Default: false.
+    pub event_versioning: bool,
+    /// Emit a one-time `tpc_hint` event listing all offering topics at registration. Default: false.
+    pub topics_hint: bool,
+    /// Strikes after which an issuer's reporting is suspended (0 = never suspend). Default: 0.
+    pub strike_threshold: u32,
+    /// Seconds after which a pause lapses unless renewed (0 = pause never lapses). Default: 0.
+    pub max_pause_secs: u64,
+    /// Seconds after a deposit when the issuer may sweep a period's unclaimed residual
+    /// (0 = only once every holder has claimed). Default: 0.
+    pub sweep_grace_secs: u64,
+    /// Platform fee withheld from each deposit, in bps (max MAX_PLATFORM_FEE_BPS). Default: 0.
+    pub platform_fee_bps: u32,
+    /// Maximum offering metadata length in bytes (1..=MAX_METADATA_LENGTH_CEILING).
+    /// Default: MAX_METADATA_LENGTH.
+    pub max_metadata_len: u32,
+    /// Seconds after which a pending issuer transfer can no longer be accepted
+    /// (0 = never expires). Default: 0.
+    pub transfer_ttl_secs: u64,
+    /// Check at `register_offering` that the offering token answers as a token and, when it
+    /// reports a `total_supply`, that the supply is non-zero. Default: false.
+    pub verify_offering_token: bool,
+    /// Shortest claim delay `set_claim_delay` accepts. Default: 0.
+    pub min_claim_delay_secs: u64,
+    /// Longest claim delay `set_claim_delay` accepts. Default: MAX_CLAIM_DELAY_SECS.
+    pub max_claim_delay_secs: u64,
+    /// Missed scheduled periods after which `flag_delinquent` flags an offering (>= 1).
+    /// Default: DEFAULT_DELINQUENCY_PERIODS.
+    pub delinquency_periods: u32,
+    /// Reject deposits whose net of the platform fee is below
+    /// `reported * revenue_share_bps / 10_000` with
+    /// `DepositBelowReport` instead of only publishing `rev_short`. Default: false.
+    pub reject_underpayment: bool,
+    /// Page size override for paginated views (0 = the network profile's limit; at most
+    /// PAGE_LIMIT_CEILING). Default: 0.
+    pub page_limit: u32,
+    /// Periods-per-claim override (0 = the network profile's limit; at most
+    /// CLAIM_PERIODS_CEILING). Default: 0.
+    pub claim_periods: u32,
+    /// Offerings one issuer may register (0 = unlimited); counts the issuer's offering
+    /// list, including re-registrations. Default: 0.
+    pub max_offerings_per_issuer: u32,
+    /// Offering IDs that may be handed out platform-wide (0 = unlimited). Default: 0.
+    pub max_total_offerings: u64,
+}
+
+/// Partial update for `reconfigure`. `None` leaves the field unchanged.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConfigUpdate {
+    pub event_versioning: Option<bool>,
+    pub topics_hint: Option<bool>,
+    pub strike_threshold: Option<u32>,
+    pub max_pause_secs: Option<u64>,
+    pub sweep_grace_secs: Option<u64>,
+    pub platform_fee_bps: Option<u32>,
+    pub max_metadata_len: Option<u32>,
+    pub transfer_ttl_secs: Option<u64>,
+    pub verify_offering_token: Option<bool>,
+    pub min_claim_delay_secs: Option<u64>,
+    pub max_claim_delay_secs: Option<u64>,
+    pub delinquency_periods: Option<u32>,
+    pub reject_underpayment: Option<bool>,
+    pub page_limit: Option<u32>,
+    pub claim_periods: Option<u32>,
+    pub max_offerings_per_issuer: Option<u32>,
+    pub max_total_offerings: Option<u64>,
+}
+
+/// Off-chain description of an offering, set by its issuer. `Bytes` holds raw content
+/// (e.g. a 32-byte document hash) without hex/base58 encoding it into a string.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum OfferingMetadata {
+    Text(String),
+    Bytes(Bytes),
+}
+
+/// One `upgrade` in the contract's code history.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CodeRevision {
+    /// Storage layout version the code runs on: the version at upgrade time, raised by a
+    /// later `migrate`.
+    pub version: u32,
+    pub wasm_hash: BytesN<32>,
+    pub upgraded_at: u64,
+    /// Hash of the release's migration notes, as supplied to `upgrade`.
+    pub notes_hash: BytesN<32>,
+}
+
+/// Snapshot of a holder's claimable entitlement for companion contracts (e.g. lending
+/// protocols valuing future distributions). The contract cannot sign, so `digest` is not a
+/// signature: it binds the fields so a holder can sign it off-chain, and a consumer
+/// re-reading `get_claim_voucher` can check that a relayed voucher is still current.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimVoucher {
+    /// This contract, so vouchers from different deployments never collide.
+    pub contract: Address,
+    pub holder: Address,
+    pub token: Address,
+    /// Asset the entitlement pays in; `None` before the first deposit.
+    pub payment_token: Option<Address>,
+    /// Amount `claim` would pay now (`get_claimable`).
+    pub claimable: i128,
+    /// Claims the holder has made on the offering; any claim makes older vouchers stale.
+    pub nonce: u32,
+    /// Ledger sequence the snapshot was taken at. Informational; not part of `digest`.
+    pub ledger: u32,
+    /// `sha256(xdr((contract, holder, token, claimable, nonce)))`.
+    pub digest: BytesN<32>,
+}
+
+/// One entry in an offering's metadata history.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetadataRevision {
+    pub metadata: OfferingMetadata,
+    /// Ledger timestamp of the `set_offering_metadata` call.
+    pub set_at: u64,
+}
+
+/// Structured offering description for indexers, kept alongside the free-form
+/// `OfferingMetadata` rather than replacing it. URIs are stored as given; empty strings
+/// mean "not provided".
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfferingMetadataV2 {
+    pub name: String,
+    pub symbol: String,
+    pub description_uri: String,
+    pub logo_uri: String,
+    /// Free-form category tag, e.g. `real_estate` or `music`.
+    pub category: Symbol,
+    pub website: String,
 }
 
 /// Storage keys: offerings use OfferCount/OfferItem; blacklist uses Blacklist(token).
 /// Multi-period claim keys use PeriodRevenue/PeriodEntry/PeriodCount for per-offering
 /// period tracking, HolderShare for holder allocations, LastClaimedIdx for claim progress,
 /// and PaymentToken for the token used to pay out revenue.
+///
+/// `DataKey` is close to the contract spec's 50-variant limit, so newer keys live in the
+/// topic enums below (`ClaimKey`, `EscrowKey`, `OfferingKey`, ...). Add a key to the enum
+/// for its topic rather than here or in a new enum.
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -171,14 +874,332 @@ pub enum DataKey {
     OfferingIssuer(Address),
     /// Testnet mode flag; when true, enables fee-free/simplified behavior (#24).
     TestnetMode,
-    /// Safety role address for emergency pause (#7).
+    /// Single safety address from deployments before `SafetySet`; read as a fallback only.
     Safety,
     /// Global pause flag; when true, state-mutating ops are disabled (#7).
     Paused,
     /// Feature flag: emit versioned events when present (v1 schema).
     EventVersioningEnabled,
+    /// Highest initialization step completed (1 = `initialize`, 2 = `initialize_v2`).
+    InitVersion,
+    /// Config schema version that last wrote settings via `reconfigure`.
+    ConfigVersion,
+    /// Global storage usage counters (`StorageMetrics`).
+    StorageMetrics,
+    /// Config flag: emit the `tpc_hint` event at offering registration.
+    TopicsHintEnabled,
+    /// Config: strike count that suspends an issuer's reporting (0 = disabled).
+    StrikeThreshold,
+    /// Per issuer: misbehavior counters (`IssuerReputation`).
+    IssuerReputation(Address),
+    /// Capital raise for an offering token (`CapitalRaise`).
+    Raise(Address),
+    /// Unsettled subscription amount for (offering_token, investor).
+    RaiseContribution(Address, Address),
+    /// Per offering token: secondary transfer policy (`ShareTransferConfig`).
+    ShareTransferConfig(Address),
+    /// Share transfer record for (offering_token, transfer_id).
+    ShareTransfer(Address, u32),
+    /// Number of share transfers proposed for an offering token.
+    ShareTransferCount(Address),
+    /// Share sale listing for (offering_token, listing_id).
+    ShareListing(Address, u32),
+    /// Number of share listings created for an offering token.
+    ShareListingCount(Address),
+    /// Number of distinct holders ever assigned a share for an offering token.
+    HolderCount(Address),
+    /// Holder registry: (offering_token, index) -> holder, in first-assignment order.
+    HolderAt(Address, u32),
+    /// Claims paused for (offering_token, period_id) when present.
+    PeriodPaused(Address, u64),
+    /// Reverse of PeriodEntry: (offering_token, period_id) -> index.
+    PeriodIndex(Address, u64),
+    /// Amount pushed by distribute_batch for (offering_token, period_id, holder).
+    PushPaid(Address, u64, Address),
+    /// Sum of all holder share_bps for an offering.
+    TotalShareBps(Address),
+    /// Safety addresses allowed to pause/unpause. Supersedes the single `Safety` entry.
+    SafetySet,
+    /// Maximum pause duration in seconds (0 = unlimited).
+    MaxPauseSecs,
+    /// Ledger timestamp of the latest pause (or renewal).
+    PausedAt,
+    /// Grace period in seconds before an unclaimed period residual may be swept.
+    SweepGraceSecs,
+    /// Network profile chosen by `initialize_v3`.
+    NetworkProfile,
+}
+
+/// Claim-related keys.
+#[contracttype]
+#[derive(Clone)]
+pub enum ClaimKey {
+    /// Delegate allowed to trigger claims for (offering_token, holder).
+    Delegate(Address, Address),
+    /// Payout destination override for (offering_token, holder).
+    PayoutAddress(Address, Address),
+    /// Donation split (`DonationSplit`) applied to claims for (offering_token, holder).
+    Donation(Address, Address),
+    /// Sub-account breakdown (`Vec<SubAccount>`) of a custodian's share for
+    /// (offering_token, custodian).
+    SubAccounts(Address, Address),
+    /// Prompt-claim bonus (`ClaimBonus`) for an offering token.
+    Bonus(Address),
+    /// Seconds over which each period of an offering token unlocks; absent when claims
+    /// pay whole periods.
+    StreamSecs(Address),
+    /// Payment-token amount that buys 1 bps of holder share when reinvesting, per
+    /// offering token.
+    ReinvestRate(Address),
+    /// Present while (offering_token, holder) reinvests its claims.
+    AutoReinvest(Address, Address),
+    /// Shortest accepted claim delay (`ContractConfig.min_claim_delay_secs`).
+    MinDelaySecs,
+    /// Longest accepted claim delay (`ContractConfig.max_claim_delay_secs`).
+    MaxDelaySecs,
+    /// Claim delay steps (`Vec<(from_timestamp, secs)>`) for an offering token, oldest
+    /// first with rising delays. A period uses the last step at or before its deposit.
+    DelaySchedule(Address),
+    /// `ClaimOrder` for an offering token; absent means oldest first.
+    Order(Address),
+    /// Present while (offering_token, holder) prefers newest-first claims under
+    /// `ClaimOrder::HolderChoice`.
+    NewestFirst(Address, Address),
+    /// Period indices `[lo, hi)` above the holder's `LastClaimedIdx` already settled by
+    /// newest-first claims, for (offering_token, holder).
+    SettledBand(Address, Address),
+    /// Payment-token balance set aside by the issuer to pay claim bonuses.
+    BonusPool(Address),
+    /// Number of claim receipts for (offering_token, holder).
+    HistoryCount(Address, Address),
+    /// Claim receipt (`ClaimRecord`) for (offering_token, holder, seq).
+    History(Address, Address, u32),
+    /// Highest `LastClaimedIdx` any holder has reached for an offering token. Late deposits
+    /// are never inserted below it, so already-claimed periods keep their indices.
+    ClaimWatermark(Address),
+    /// Ledger timestamp at which `freeze` ran, which opens the emergency withdrawal
+    /// window (instance storage).
+    FrozenAt,
+    /// Present once a holder made their emergency withdrawal for (offering_token, holder).
+    EmergencyWithdrawn(Address, Address),
+}
+
+/// Per-period escrow accounting keys.
+#[contracttype]
+#[derive(Clone)]
+pub enum EscrowKey {
+    /// Total paid out for (offering_token, period_id) by claims, pushes and proofs.
+    Distributed(Address, u64),
+    /// Residual swept or reclaimed for (offering_token, period_id); present once closed.
+    Swept(Address, u64),
+    /// Seconds after deposit that an offering's periods stay claimable (0 = no expiry).
+    ClaimExpiry(Address),
+    /// Where `reclaim_expired` sends unclaimed revenue for an offering; issuer if unset.
+    ReclaimTreasury(Address),
+    /// Platform fee withheld from the deposit for (offering_token, period_id).
+    Fee(Address, u64),
+    /// Revenue reported for (offering_token, period_id) when it was deposited.
+    Reported(Address, u64),
+    /// Present while deposits below the reported revenue share are rejected
+    /// (`ContractConfig.reject_underpayment`).
+    RejectUnderpayment,
+    /// Share bps (offering_token, seller) has on offer in active listings and pending
+    /// share transfers; absent when nothing is committed.
+    CommittedBps(Address, Address),
+}
+
+/// Storage keys for Merkle-root distributions.
+#[contracttype]
+#[derive(Clone)]
+pub enum MerkleKey {
+    /// Distribution root for (offering_token, period_id).
+    Root(Address, u64),
+    /// Set once (offering_token, period_id, holder) has claimed with a proof.
+    Claimed(Address, u64, Address),
+    /// Total paid by proof claims for (offering_token, period_id).
+    ClaimedTotal(Address, u64),
+    /// Source period whose root (offering_token, period_id) reuses via `reuse_snapshot`.
+    ReusedFrom(Address, u64),
+}
+
+/// Storage keys for balance-snapshot distributions.
+#[contracttype]
+#[derive(Clone)]
+pub enum SnapshotKey {
+    /// Recorded balance for (offering_token, snapshot_ref, holder).
+    Balance(Address, u64, Address),
+    /// Total supply the balances of (offering_token, snapshot_ref) are measured against.
+    Supply(Address, u64),
+    /// Sum of balances recorded so far for (offering_token, snapshot_ref).
+    Recorded(Address, u64),
+    /// Present once (offering_token, snapshot_ref) backs a period; no further uploads.
+    Sealed(Address, u64),
+    /// Snapshot that (offering_token, period_id) pays against.
+    ForPeriod(Address, u64),
+    /// Set once (offering_token, period_id, holder) has claimed from the snapshot.
+    Claimed(Address, u64, Address),
+    /// Total supply for balance-derived shares; present while the mode is on for an
+    /// offering token.
+    LiveSupply(Address),
+    /// `LiveSupply` captured when (offering_token, period_id) was deposited.
+    PeriodSupply(Address, u64),
+}
+
+/// Platform fee keys.
+#[contracttype]
+#[derive(Clone)]
+pub enum FeeKey {
+    /// Platform fee in bps withheld from deposits (`ContractConfig.platform_fee_bps`).
+    Bps,
+    /// Fees accrued and not yet withdrawn, per payment token.
+    Accrued(Address),
+    /// Fee override in bps for one offering token; supersedes `Bps` when present.
+    OfferingBps(Address),
+    /// Referrer of an offering token and its cut of the platform fee in bps.
+    Referrer(Address),
+    /// Referral fees accrued and not yet claimed, per (referrer, payment_token).
+    ReferralAccrued(Address, Address),
+}
+
+/// Offering lifecycle keys.
+#[contracttype]
+#[derive(Clone)]
+pub enum OfferingKey {
+    /// Present once the issuer has closed the offering.
+    Closed(Address),
+    /// Present while the offering is paused by its issuer or the admin.
+    Paused(Address),
+    /// Offering tokens with an issuer transfer pending to this address.
+    TransferInbox(Address),
+    /// Number of issuer transfers pending across all offerings.
+    PendingTransferCount,
+    /// Global pending-transfer index: position -> offering token.
+    PendingTransferAt(u32),
+    /// Reverse of `PendingTransferAt`: offering token -> position.
+    PendingTransferPos(Address),
+    /// Ledger timestamp when the pending issuer transfer for an offering token was proposed.
+    TransferProposedAt(Address),
+    /// Issuer transfer lifetime in seconds (`ContractConfig.transfer_ttl_secs`).
+    TransferTtlSecs,
+    /// `PeriodSchedule` for an offering token; absent when deposits are unscheduled.
+    PeriodSchedule(Address),
+    /// `Delinquency` of an offering token, while flagged.
+    Delinquent(Address),
+    /// Missed-period threshold (`ContractConfig.delinquency_periods`).
+    DelinquencyPeriods,
+    /// Accounting currency symbol of an offering token, e.g. `USD`.
+    Currency(Address),
+    /// Present while `register_offering` verifies the offering token
+    /// (`ContractConfig.verify_offering_token`).
+    VerifyToken,
+    /// Position of an offering token in its current issuer's `OfferItem` list.
+    ByToken(Address),
+    /// Invariant that froze an offering token, until the admin clears it.
+    InvariantFrozen(Address),
+    /// Last offering ID handed out by `register_offering`.
+    LastId,
+    /// Offering ID of an offering token.
+    Id(Address),
+    /// Offering token for an offering ID.
+    ById(u64),
+    /// `ContractConfig.max_offerings_per_issuer`, when non-zero.
+    MaxPerIssuer,
+    /// `ContractConfig.max_total_offerings`, when non-zero.
+    MaxTotal,
+}
+
+/// Offering metadata keys.
+#[contracttype]
+#[derive(Clone)]
+pub enum MetadataKey {
+    /// Metadata for an offering token.
+    Offering(Address),
+    /// Configured maximum metadata length (`ContractConfig.max_metadata_len`).
+    MaxLength,
+    /// Localized metadata for (offering_token, locale).
+    Localized(Address, Symbol),
+    /// Locales with localized metadata for an offering token, in first-set order.
+    Locales(Address),
+    /// Structured `OfferingMetadataV2` for an offering token.
+    V2(Address),
+    /// `MetadataRevision` for (offering_token, version), versions from 0.
+    Version(Address, u32),
+    /// Number of metadata versions recorded for an offering token.
+    VersionCount(Address),
+}
+
+/// Compliance keys.
+#[contracttype]
+#[derive(Clone)]
+pub enum ComplianceKey {
+    /// Compliance officer allowed to manage an offering token's blacklist.
+    Officer(Address),
+    /// Present once blacklist additions are permanently disabled for an offering token.
+    BlacklistLocked(Address),
+    /// Whitelisted investors for an offering token (`Map<Address, bool>`, like the
+    /// blacklist).
+    Whitelist(Address),
+    /// Present while an offering token's whitelist is enforced.
+    WhitelistEnabled(Address),
+    /// Present while an investor is on the platform-wide blacklist.
+    GlobalBlacklist(Address),
+    /// Timestamp until which (offering_token, holder) cannot claim.
+    Lockup(Address, Address),
+    /// External KYC attestation registry, set by the admin.
+    KycRegistry,
+    /// Present while an offering token requires holders to be attested in the registry.
+    KycRequired(Address),
+    /// `Restrictions` for an offering token; absent when unrestricted.
+    Restrictions(Address),
+    /// `HolderProfile` for (offering_token, holder).
+    HolderProfile(Address, Address),
+    /// `OfferingTerms` for an offering token.
+    Terms(Address),
+    /// Latest `TermsAcknowledgement` for (offering_token, holder).
+    TermsAck(Address, Address),
+}
+
+/// Report attestation keys.
+#[contracttype]
+#[derive(Clone)]
+pub enum AuditKey {
+    /// Auditors an offering token's issuer has approved, in added order.
+    Auditors(Address),
+    /// `Attestation`s for (offering_token, period_id), one per auditor.
+    Attestations(Address, u64),
+}
+
+/// Admin overrides of the network profile's per-call limits. Held in instance storage,
+/// which loads with the contract, so the hot claim and page paths pay no extra entry read.
+#[contracttype]
+#[derive(Clone)]
+pub enum LimitKey {
+    /// `ContractConfig.page_limit`, when non-zero.
+    PageLimit,
+    /// `ContractConfig.claim_periods`, when non-zero.
+    ClaimPeriods,
+}
+
+/// Governance keys: role membership, storage version and code upgrades.
+#[contracttype]
+#[derive(Clone)]
+pub enum AdminKey {
+    /// Present while the address holds the role.
+    RoleMember(Role, Address),
+    /// Storage layout version written by `initialize` and `migrate` (absent = 1).
+    ContractVersion,
+    /// `CodeRevision`s installed by `upgrade`, oldest first.
+    UpgradeHistory,
 }
 
+/// Storage layout version this code expects. `migrate` brings older storage up to it.
+/// Version 2 folds the legacy single `Safety` entry into `SafetySet`; version 3 moves
+/// `Admin`, `Paused` and `Frozen` into instance storage.
+const CONTRACT_VERSION: u32 = 3;
+
+/// Current `ContractConfig` schema version. Bump when adding config fields.
+const CONFIG_VERSION: u32 = 14;
+
 /// Maximum number of offerings returned in a single page.
 const MAX_PAGE_LIMIT: u32 = 20;
 
@@ -186,563 +1207,959 @@ const MAX_PAGE_LIMIT: u32 = 20;
 /// Keeps compute costs predictable within Soroban limits.
 const MAX_CLAIM_PERIODS: u32 = 50;
 
-#[contract]
-pub struct RevoraRevenueShare;
+/// Maximum period entries `deposit_revenue` shifts to keep periods in `period_id` order.
+const MAX_PERIOD_REORDER: u32 = 50;
 
-#[contractimpl]
-impl RevoraRevenueShare {
-    fn is_event_versioning_enabled(env: Env) -> bool {
-        let key = DataKey::EventVersioningEnabled;
-        env.storage()
-            .persistent()
-            .get::<DataKey, bool>(&key)
-            .unwrap_or(false)
-    }
+/// Maximum Merkle proof depth accepted by `claim_with_proof` (2^32 leaves).
+const MAX_MERKLE_PROOF_LEN: u32 = 32;
 
-    /// Returns error if contract is frozen (#32). Call at start of state-mutating entrypoints.
-    fn require_not_frozen(env: &Env) -> Result<(), RevoraError> {
-        let key = DataKey::Frozen;
-        if env
-            .storage()
-            .persistent()
-            .get::<DataKey, bool>(&key)
-            .unwrap_or(false)
-        {
-            return Err(RevoraError::ContractFrozen);
-        }
-        Ok(())
-    }
+/// Maximum holders read by `compute_concentration` in one call.
+const MAX_CONCENTRATION_HOLDERS: u32 = 100;
 
-    /// Get the current issuer for an offering token (used for auth checks after transfers).
-    fn get_current_issuer(env: &Env, token: &Address) -> Option<Address> {
-        let key = DataKey::OfferingIssuer(token.clone());
-        env.storage().persistent().get(&key)
-    }
+/// Maximum recipients paid by one `distribute_batch` call.
+const MAX_DISTRIBUTE_BATCH: u32 = 50;
 
-    /// Initialize admin and optional safety role for emergency pause (#7).
-    /// Can only be called once; panics if already initialized.
-    pub fn initialize(env: Env, admin: Address, safety: Option<Address>) {
-        if env.storage().persistent().has(&DataKey::Admin) {
-            panic!("already initialized");
-        }
-        env.storage()
-            .persistent()
-            .set(&DataKey::Admin, &admin.clone());
-        if let Some(s) = safety.clone() {
-            env.storage().persistent().set(&DataKey::Safety, &s);
-        }
-        env.storage().persistent().set(&DataKey::Paused, &false);
-        env.events().publish((EVENT_INIT, admin.clone()), (safety,));
-    }
+/// Maximum entries accepted by one `set_holder_shares_bulk` call.
+const MAX_BULK_SHARES: u32 = 50;
 
-    /// Pause the contract (admin only). Idempotent.
-    pub fn pause_admin(env: Env, caller: Address) {
-        caller.require_auth();
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Admin)
-            .expect("admin not set");
-        if caller != admin {
-            panic!("not admin");
+/// Maximum number of safety addresses.
+const MAX_SAFETY_ADDRESSES: u32 = 10;
+
+/// Maximum pending issuer transfers addressed to one account.
+const MAX_TRANSFER_INBOX: u32 = 20;
+
+/// Maximum investors per blacklist/whitelist bulk call.
+const MAX_LIST_BATCH: u32 = 50;
+
+/// Maximum steps kept in an offering's claim delay schedule; the oldest two merge, taking
+/// the shorter delay, beyond it.
+const MAX_DELAY_STEPS: u32 = 16;
+
+/// Maximum jurisdictions in an offering's `Restrictions`.
+const MAX_JURISDICTIONS: u32 = 32;
+
+/// Maximum sub-accounts in a custodian's breakdown.
+const MAX_SUB_ACCOUNTS: u32 = 20;
+
+/// Maximum offerings `claim_all` claims in one call.
+const MAX_CLAIM_ALL_TOKENS: u32 = 10;
+
+/// Maximum auditors per offering.
+const MAX_AUDITORS: u32 = 10;
+
+/// Maximum platform fee (10%) accepted by `reconfigure`.
+const MAX_PLATFORM_FEE_BPS: u32 = 1_000;
+
+/// Default longest claim delay (365 days) accepted by `set_claim_delay`.
+const MAX_CLAIM_DELAY_SECS: u64 = 365 * 24 * 60 * 60;
+
+/// How long (180 days) the contract must have been frozen before `emergency_withdraw` opens.
+const EMERGENCY_WITHDRAW_DELAY_SECS: u64 = 180 * 24 * 60 * 60;
+
+/// Default `ContractConfig.delinquency_periods`.
+const DEFAULT_DELINQUENCY_PERIODS: u32 = 2;
+
+/// Most recent scheduled periods `get_missed_periods` checks.
+const MAX_MISSED_SCAN: u64 = 100;
+
+/// Maximum prompt-claim bonus (10% of the payout) accepted by `set_claim_bonus`.
+const MAX_CLAIM_BONUS_BPS: u32 = 1_000;
+
+/// Default maximum offering metadata length in bytes.
+const MAX_METADATA_LENGTH: u32 = 256;
+
+/// Largest `max_metadata_len` accepted by `reconfigure`.
+const MAX_METADATA_LENGTH_CEILING: u32 = 4_096;
+
+/// Highest page size `reconfigure` accepts for `page_limit`.
+const PAGE_LIMIT_CEILING: u32 = 100;
+
+/// Highest periods-per-claim `reconfigure` accepts for `claim_periods`.
+const CLAIM_PERIODS_CEILING: u32 = 200;
+
+/// Maximum locales with localized metadata per offering.
+const MAX_METADATA_LOCALES: u32 = 10;
+
+impl NetworkProfile {
+    /// Built-in limits for the profile. Testnet and Futurenet use the full `MAX_*`
+    /// ceilings; Mainnet halves them.
+    fn limits(self) -> NetworkLimits {
+        match self {
+            NetworkProfile::Testnet | NetworkProfile::Futurenet => NetworkLimits {
+                page_limit: MAX_PAGE_LIMIT,
+                claim_periods: MAX_CLAIM_PERIODS,
+                distribute_batch: MAX_DISTRIBUTE_BATCH,
+                bulk_shares: MAX_BULK_SHARES,
+            },
+            NetworkProfile::Mainnet => NetworkLimits {
+                page_limit: MAX_PAGE_LIMIT / 2,
+                claim_periods: MAX_CLAIM_PERIODS / 2,
+                distribute_batch: MAX_DISTRIBUTE_BATCH / 2,
+                bulk_shares: MAX_BULK_SHARES / 2,
+            },
         }
-        env.storage().persistent().set(&DataKey::Paused, &true);
-        env.events().publish((EVENT_PAUSED, caller.clone()), ());
     }
+}
 
-    /// Unpause the contract (admin only). Idempotent.
-    pub fn unpause_admin(env: Env, caller: Address) {
-        caller.require_auth();
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Admin)
-            .expect("admin not set");
-        if caller != admin {
-            panic!("not admin");
+/// Maximum periods and holders (each) `reset_offering` will wipe in one call.
+const MAX_RESET_ENTRIES: u32 = 30;
+
+/// Largest holder registry `migrate_share_total` sums in one call.
+const MAX_BACKFILL_HOLDERS: u32 = 100;
+
+/// Largest holder index `sweep_residual` scans to confirm every holder has claimed.
+/// Bigger offerings must rely on the sweep grace window.
+const MAX_SWEEP_HOLDER_SCAN: u32 = 100;
+
+/// Ledgers closed per day at ~5 seconds per ledger.
+const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Instance storage (admin, pause and freeze flags) is extended to this TTL when touched.
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Offering, period and holder entries are extended to this TTL when touched.
+const PERSISTENT_BUMP_AMOUNT: u32 = 120 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - 30 * DAY_IN_LEDGERS;
+
+/// Order one claim visits a holder's pending periods in, and the run of fully settled
+/// periods it builds. Oldest first walks up from the cursor; newest first walks down from
+/// the last period, skipping the band settled by earlier newest-first claims. Shared by
+/// `do_claim` and `estimate_claim_cost`.
+struct ClaimWalk {
+    newest: bool,
+    start: u32,
+    count: u32,
+    band: (u32, u32),
+    run: Option<(u32, u32)>,
+    run_open: bool,
+}
+
+impl ClaimWalk {
+    fn new(newest: bool, start: u32, count: u32, band: Option<(u32, u32)>) -> Self {
+        let band = match band {
+            Some((lo, hi)) if newest && hi > start && hi <= count => (lo.max(start), hi),
+            _ => (count, count),
+        };
+        ClaimWalk {
+            newest,
+            start,
+            count,
+            band,
+            run: None,
+            run_open: true,
         }
-        env.storage().persistent().set(&DataKey::Paused, &false);
-        env.events().publish((EVENT_UNPAUSED, caller.clone()), ());
     }
 
-    /// Pause the contract (safety role only). Idempotent.
-    pub fn pause_safety(env: Env, caller: Address) {
-        caller.require_auth();
-        let safety: Address = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Safety)
-            .expect("safety not set");
-        if caller != safety {
-            panic!("not safety");
+    /// Periods left to visit.
+    fn len(&self) -> u32 {
+        (self.count - self.band.1) + (self.band.0 - self.start)
+    }
+
+    /// Period index visited at step `k`.
+    fn index(&self, k: u32) -> u32 {
+        if !self.newest {
+            return self.start + k;
+        }
+        let above = self.count - self.band.1;
+        if k < above {
+            self.count - 1 - k
+        } else {
+            self.band.0 - 1 - (k - above)
         }
-        env.storage().persistent().set(&DataKey::Paused, &true);
-        env.events().publish((EVENT_PAUSED, caller.clone()), ());
     }
 
-    /// Unpause the contract (safety role only). Idempotent.
-    pub fn unpause_safety(env: Env, caller: Address) {
-        caller.require_auth();
-        let safety: Address = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Safety)
-            .expect("safety not set");
-        if caller != safety {
-            panic!("not safety");
+    /// Note whether the period just visited ended fully paid. The run grows while settled
+    /// periods follow each other (across the old band); the first gap after it closes it.
+    fn visit(&mut self, i: u32, settled: bool) {
+        if !self.run_open {
+            return;
+        }
+        if !settled {
+            self.run_open = self.run.is_none();
+            return;
         }
-        env.storage().persistent().set(&DataKey::Paused, &false);
-        env.events().publish((EVENT_UNPAUSED, caller.clone()), ());
+        self.run = match self.run {
+            None => Some((i, i + 1)),
+            Some((lo, hi)) if i + 1 == lo || (lo == self.band.1 && i + 1 == self.band.0) => {
+                Some((i, hi))
+            }
+            run => {
+                self.run_open = false;
+                run
+            }
+        };
     }
 
-    /// Query the paused state of the contract.
-    pub fn is_paused(env: Env) -> bool {
+    /// Cursor and band after a newest-first walk. The run, merged with the old band when
+    /// they touch, moves the cursor once it reaches it and otherwise becomes the band.
+    fn finish(&self) -> (u32, Option<(u32, u32)>) {
+        let old = if self.band.0 < self.band.1 {
+            Some(self.band)
+        } else {
+            None
+        };
+        let settled = match (self.run, old) {
+            (Some((lo, hi)), Some((b_lo, b_hi))) if lo <= b_hi && b_lo <= hi => {
+                Some((lo.min(b_lo), hi.max(b_hi)))
+            }
+            (Some(run), _) => Some(run),
+            (None, old) => old,
+        };
+        match settled {
+            Some((lo, hi)) if lo <= self.start => (hi, None),
+            band => (self.start, band),
+        }
+    }
+}
+
+#[contract]
+pub struct RevoraRevenueShare;
+
+/// Shared internal helpers. Entrypoints live in the `offerings`, `revenue`, `claims`,
+/// `compliance`, `governance` and `queries` modules.
+impl RevoraRevenueShare {
+    fn is_event_versioning_enabled(env: Env) -> bool {
+        let key = DataKey::EventVersioningEnabled;
         env.storage()
             .persistent()
-            .get::<DataKey, bool>(&DataKey::Paused)
+            .get::<DataKey, bool>(&key)
             .unwrap_or(false)
     }
 
-    /// Helper: panic if contract is paused. Used by state-mutating entrypoints.
-    fn require_not_paused(env: &Env) {
-        if env
-            .storage()
-            .persistent()
-            .get::<DataKey, bool>(&DataKey::Paused)
-            .unwrap_or(false)
-        {
-            panic!("contract is paused");
+    /// Returns error if contract is frozen (#32). Call at start of state-mutating entrypoints.
+    fn require_not_frozen(env: &Env) -> Result<(), RevoraError> {
+        if Self::read_flag(env, DataKey::Frozen) {
+            return Err(RevoraError::ContractFrozen);
         }
+        Ok(())
     }
 
-    /// Register a new revenue-share offering.
-    /// Returns `Err(RevoraError::InvalidRevenueShareBps)` if revenue_share_bps > 10000.
-    /// In testnet mode, bps validation is skipped to allow flexible testing.
-    pub fn register_offering(
-        env: Env,
-        issuer: Address,
-        token: Address,
-        revenue_share_bps: u32,
-        payout_asset: Address,
-    ) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
-        Self::require_not_paused(&env);
-        issuer.require_auth();
+    /// Stored admin. Lives in instance storage; deployments that have not run `migrate`
+    /// to version 3 still hold it in persistent storage.
+    fn read_admin(env: &Env) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .or_else(|| env.storage().persistent().get(&DataKey::Admin))
+    }
 
-        // Skip bps validation in testnet mode
-        let testnet_mode = Self::is_testnet_mode(env.clone());
-        if !testnet_mode && revenue_share_bps > 10_000 {
-            return Err(RevoraError::InvalidRevenueShareBps);
-        }
+    /// Global `Paused` / `Frozen` flag, with the same legacy fallback as `read_admin`.
+    fn read_flag(env: &Env, key: DataKey) -> bool {
+        env.storage()
+            .instance()
+            .get::<DataKey, bool>(&key)
+            .or_else(|| env.storage().persistent().get::<DataKey, bool>(&key))
+            .unwrap_or(false)
+    }
 
-        let count_key = DataKey::OfferCount(issuer.clone());
-        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    /// Write a global `Admin` / `Paused` / `Frozen` entry and keep the instance alive.
+    fn write_instance<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, val: &V) {
+        env.storage().instance().set(key, val);
+        Self::extend_instance(env);
+    }
 
-        let offering = Offering {
-            issuer: issuer.clone(),
-            token: token.clone(),
-            revenue_share_bps,
-            payout_asset: payout_asset.clone(),
-        };
+    fn extend_instance(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
 
-        let item_key = DataKey::OfferItem(issuer.clone(), count);
-        env.storage().persistent().set(&item_key, &offering);
-        env.storage().persistent().set(&count_key, &(count + 1));
+    /// Extend a persistent entry's TTL if it exists; missing entries are skipped.
+    fn extend_persistent<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        let store = env.storage().persistent();
+        if store.has(key) {
+            store.extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        }
+    }
 
-        // Maintain reverse lookup: token -> issuer
-        let issuer_lookup_key = DataKey::OfferingIssuer(token.clone());
-        env.storage().persistent().set(&issuer_lookup_key, &issuer);
+    /// Extend the offering-level entries that every deposit and claim reads.
+    fn extend_offering_ttl(env: &Env, issuer: &Address, token: &Address) {
+        Self::extend_instance(env);
+        Self::extend_persistent(env, &DataKey::OfferCount(issuer.clone()));
+        if let Some(index) = Self::find_offering_index(env, issuer, token) {
+            Self::extend_persistent(env, &DataKey::OfferItem(issuer.clone(), index));
+        }
+        Self::extend_persistent(env, &OfferingKey::ByToken(token.clone()));
+        Self::extend_persistent(env, &DataKey::OfferingIssuer(token.clone()));
+        Self::extend_persistent(env, &DataKey::PaymentToken(token.clone()));
+        Self::extend_persistent(env, &DataKey::PeriodCount(token.clone()));
+        Self::extend_persistent(env, &DataKey::HolderCount(token.clone()));
+        Self::extend_persistent(env, &DataKey::TotalShareBps(token.clone()));
+        Self::extend_persistent(env, &DataKey::ClaimDelaySecs(token.clone()));
+        Self::extend_persistent(env, &ClaimKey::DelaySchedule(token.clone()));
+        Self::extend_persistent(env, &DataKey::Blacklist(token.clone()));
+        Self::extend_persistent(env, &ClaimKey::ClaimWatermark(token.clone()));
+    }
 
-        env.events().publish(
-            (symbol_short!("offer_reg"), issuer.clone()),
-            (token.clone(), revenue_share_bps, payout_asset.clone()),
+    /// Extend one holder's share, claim cursor and payout routing for an offering.
+    fn extend_holder_ttl(env: &Env, token: &Address, holder: &Address) {
+        Self::extend_persistent(env, &DataKey::HolderShare(token.clone(), holder.clone()));
+        Self::extend_persistent(env, &DataKey::LastClaimedIdx(token.clone(), holder.clone()));
+        Self::extend_persistent(env, &ClaimKey::PayoutAddress(token.clone(), holder.clone()));
+        Self::extend_persistent(env, &ClaimKey::Delegate(token.clone(), holder.clone()));
+    }
+
+    /// Extend the entries a claim reads for one deposited period.
+    /// All four entries exist for every deposited period, so no `has` checks are needed.
+    fn extend_period_ttl(env: &Env, token: &Address, index: u32, period_id: u64) {
+        let store = env.storage().persistent();
+        let (threshold, amount) = (PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        store.extend_ttl(
+            &DataKey::PeriodEntry(token.clone(), index),
+            threshold,
+            amount,
         );
-        // Optionally emit a versioned v1 event with explicit version field
-        if Self::is_event_versioning_enabled(env.clone()) {
-            env.events().publish(
-                (EVENT_OFFER_REG_V1, issuer.clone()),
-                (
-                    EVENT_SCHEMA_VERSION,
-                    token.clone(),
-                    revenue_share_bps,
-                    payout_asset.clone(),
-                ),
-            );
+        store.extend_ttl(
+            &DataKey::PeriodIndex(token.clone(), period_id),
+            threshold,
+            amount,
+        );
+        store.extend_ttl(
+            &DataKey::PeriodRevenue(token.clone(), period_id),
+            threshold,
+            amount,
+        );
+        store.extend_ttl(
+            &DataKey::PeriodDepositTime(token.clone(), period_id),
+            threshold,
+            amount,
+        );
+    }
+
+    /// Fails with `OfferingClosed` once the issuer has closed the offering.
+    fn require_offering_open(env: &Env, token: &Address) -> Result<(), RevoraError> {
+        if env
+            .storage()
+            .persistent()
+            .has(&OfferingKey::Closed(token.clone()))
+        {
+            return Err(RevoraError::OfferingClosed);
         }
         Ok(())
     }
 
-    /// Fetch a single offering by issuer and token (scans issuer's offerings).
-    pub fn get_offering(env: Env, issuer: Address, token: Address) -> Option<Offering> {
-        let count = Self::get_offering_count(env.clone(), issuer.clone());
-        for i in 0..count {
-            let item_key = DataKey::OfferItem(issuer.clone(), i);
-            let offering: Offering = env.storage().persistent().get(&item_key).unwrap();
-            if offering.token == token {
-                return Some(offering);
-            }
-        }
-        None
+    /// Get the current issuer for an offering token (used for auth checks after transfers).
+    fn get_current_issuer(env: &Env, token: &Address) -> Option<Address> {
+        let key = DataKey::OfferingIssuer(token.clone());
+        env.storage().persistent().get(&key)
     }
 
-    /// List all offering tokens for an issuer.
-    pub fn list_offerings(env: Env, issuer: Address) -> Vec<Address> {
-        let (page, _) = Self::get_offerings_page(env.clone(), issuer.clone(), 0, MAX_PAGE_LIMIT);
-        let mut tokens = Vec::new(&env);
-        for i in 0..page.len() {
-            tokens.push_back(page.get(i).unwrap().token);
-        }
-        tokens
+    /// Every event symbol an operation on an offering can emit (legacy and versioned),
+    /// published once at registration so indexers can build topic filters without
+    /// hard-coding the list. Contract-wide events (init, config, testnet mode, roles,
+    /// platform pause and freeze, safety set, global blacklist, KYC registry, upgrades,
+    /// issuer reinstatement, fee and referral withdrawals) are left out.
+    fn offering_event_topics(env: &Env) -> Vec<Symbol> {
+        Vec::from_array(
+            env,
+            [
+                // Registration and reporting
+                symbol_short!("offer_reg"),
+                EVENT_OFFER_REG_V1,
+                EVENT_TOPICS_HINT,
+                EVENT_REVENUE_REPORTED,
+                EVENT_REVENUE_REPORTED_ASSET,
+                EVENT_REVENUE_REPORT_INITIAL,
+                EVENT_REVENUE_REPORT_INITIAL_ASSET,
+                EVENT_REVENUE_REPORT_OVERRIDE,
+                EVENT_REVENUE_REPORT_OVERRIDE_ASSET,
+                EVENT_REVENUE_REPORT_REJECTED,
+                EVENT_REVENUE_REPORT_REJECTED_ASSET,
+                EVENT_REV_INIT_V1,
+                EVENT_REV_INIA_V1,
+                EVENT_REV_REP_V1,
+                EVENT_REV_REPA_V1,
+                EVENT_REV_REP_V2,
+                EVENT_REPORT_ATTESTED,
+                EVENT_AUDITOR_ADDED,
+                EVENT_AUDITOR_REMOVED,
+                EVENT_STRIKE,
+                EVENT_ISSUER_SUSPENDED,
+                EVENT_CONCENTRATION_WARNING,
+                // Deposits and fees
+                EVENT_REV_DEPOSIT,
+                EVENT_REV_DEPOSIT_V2,
+                EVENT_REV_SHORT,
+                EVENT_FEE_ACCRUED,
+                EVENT_OFFERING_FEE_SET,
+                EVENT_REFERRER_SET,
+                EVENT_REFERRAL_ACCRUED,
+                EVENT_SCHEDULE_SET,
+                EVENT_DELINQUENT,
+                EVENT_DELINQUENCY_CLEARED,
+                EVENT_CURRENCY_SET,
+                // Holder shares and claims
+                EVENT_SHARE_SET,
+                EVENT_CLAIM,
+                EVENT_CLAIM_V2,
+                EVENT_CLAIM_DELAY_SET,
+                EVENT_CLAIM_ORDER,
+                EVENT_CLAIM_ORDER_PREF,
+                EVENT_CLAIM_EXPIRY_SET,
+                EVENT_DIST_CALC,
+                EVENT_DISTRIBUTE_BATCH,
+                EVENT_DELEGATE_SET,
+                EVENT_DELEGATE_REVOKED,
+                EVENT_DELEGATED_CLAIM,
+                EVENT_PAYOUT_ADDRESS_SET,
+                EVENT_DONATION_SET,
+                EVENT_DONATED,
+                EVENT_SUB_ACCOUNTS_SET,
+                EVENT_SUB_ALLOCATED,
+                EVENT_BONUS_SET,
+                EVENT_BONUS_FUNDED,
+                EVENT_BONUS_WITHDRAWN,
+                EVENT_BONUS_PAID,
+                EVENT_STREAM_SET,
+                EVENT_BALANCE_MODE,
+                EVENT_REINVEST_RATE_SET,
+                EVENT_REINVEST_SET,
+                EVENT_REINVESTED,
+                EVENT_EMERGENCY_WITHDRAW,
+                // Periods, proofs and snapshots
+                EVENT_PERIOD_PAUSED,
+                EVENT_PERIOD_UNPAUSED,
+                EVENT_ROOT_SET,
+                EVENT_PROOF_CLAIM,
+                EVENT_SNAPSHOT_BALANCES,
+                EVENT_SNAPSHOT_SET,
+                EVENT_SNAPSHOT_CLAIM,
+                EVENT_SNAPSHOT_REUSED,
+                EVENT_RESIDUAL_SWEPT,
+                EVENT_RECLAIM_TREASURY_SET,
+                EVENT_EXPIRED_RECLAIMED,
+                // Compliance
+                EVENT_BL_ADD,
+                EVENT_BL_REM,
+                EVENT_BL_ADD_BULK,
+                EVENT_BL_REM_BULK,
+                EVENT_BLACKLIST_LOCKED,
+                EVENT_COMPLIANCE_OFFICER_SET,
+                EVENT_WL_ADD,
+                EVENT_WL_REM,
+                EVENT_WL_ADD_BULK,
+                EVENT_WL_REM_BULK,
+                EVENT_WL_ENABLED,
+                EVENT_HOLDER_LOCKUP,
+                EVENT_KYC_REQUIRED,
+                EVENT_RESTRICTIONS_SET,
+                EVENT_PROFILE_SET,
+                EVENT_TERMS_SET,
+                EVENT_TERMS_ACK,
+                // Lifecycle, metadata and issuer transfers
+                EVENT_METADATA_SET,
+                EVENT_METADATA_LOCALE_SET,
+                EVENT_METADATA_V2_SET,
+                EVENT_ISSUER_TRANSFER_PROPOSED,
+                EVENT_ISSUER_TRANSFER_ACCEPTED,
+                EVENT_ISSUER_TRANSFER_CANCELLED,
+                EVENT_OFFERING_CLOSED,
+                EVENT_OFFERING_PAUSED,
+                EVENT_OFFERING_UNPAUSED,
+                EVENT_OFFERING_RESET,
+                EVENT_INVARIANT_FROZEN,
+                EVENT_INVARIANT_CLEARED,
+                EVENT_MIGRATED,
+                // Raises and secondary trading
+                EVENT_RAISE_OPENED,
+                EVENT_RAISE_SUBSCRIBED,
+                EVENT_RAISE_FINALIZED,
+                EVENT_RAISE_SETTLED,
+                EVENT_SHARE_XFER_CFG,
+                EVENT_SHARE_XFER_PROPOSED,
+                EVENT_SHARE_XFER_MATCHED,
+                EVENT_SHARE_XFER_COMPLETED,
+                EVENT_SHARE_XFER_CANCELLED,
+                EVENT_LISTING_CREATED,
+                EVENT_LISTING_FILLED,
+                EVENT_LISTING_CANCELLED,
+            ],
+        )
     }
 
-    /// Record a revenue report for an offering. Updates audit summary (#34).
-    /// Fails with `ConcentrationLimitExceeded` (#26) if concentration enforcement is on and current concentration exceeds limit.
-    /// In testnet mode, concentration enforcement is skipped.
-    /// `override_existing`: if true, allows overwriting a previously reported period.
-    pub fn report_revenue(
-        env: Env,
-        issuer: Address,
-        token: Address,
-        payout_asset: Address,
-        amount: i128,
-        period_id: u64,
-        override_existing: bool,
+    /// Persist a holder share and keep the offering's summed share bps in step. A holder's
+    /// first assignment appends them to the offering's holder registry and counts toward
+    /// the storage metrics. Raising a share fails with `ShareAllocationExceeded` when the
+    /// summed shares would exceed the offering's `allocation_cap`; offerings whose shares
+    /// predate the running total fail with `InvalidState` until `migrate_share_total` runs.
+    fn write_holder_share(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        share_bps: u32,
     ) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
-
-        // Verify offering exists and issuer is current
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
-
-        if current_issuer != issuer {
-            return Err(RevoraError::OfferingNotFound);
+        let key = DataKey::HolderShare(token.clone(), holder.clone());
+        let old_bps: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let total_key = DataKey::TotalShareBps(token.clone());
+        let total = Self::share_total(env, token)?;
+        let new_total = total
+            .checked_sub(old_bps)
+            .and_then(|t| t.checked_add(share_bps))
+            .ok_or(RevoraError::InvalidState)?;
+        if share_bps > old_bps {
+            let issuer =
+                Self::get_current_issuer(env, token).ok_or(RevoraError::OfferingNotFound)?;
+            if new_total > Self::allocation_cap(env, &issuer, token) {
+                return Err(RevoraError::ShareAllocationExceeded);
+            }
         }
-
-        Self::require_not_paused(&env);
-        issuer.require_auth();
-
-        let offering = Self::get_offering(env.clone(), issuer.clone(), token.clone())
-            .ok_or(RevoraError::OfferingNotFound)?;
-        if offering.payout_asset != payout_asset {
-            return Err(RevoraError::PayoutAssetMismatch);
+        env.storage().persistent().set(&total_key, &new_total);
+        if !env.storage().persistent().has(&key) {
+            Self::update_metrics(env, |m| m.holder_shares = m.holder_shares.saturating_add(1));
+            let count_key = DataKey::HolderCount(token.clone());
+            let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&DataKey::HolderAt(token.clone(), count), holder);
+            env.storage().persistent().set(&count_key, &(count + 1));
         }
+        env.storage().persistent().set(&key, &share_bps);
+        Self::extend_persistent(env, &key);
+        Ok(())
+    }
 
-        // Skip concentration enforcement in testnet mode
-        let testnet_mode = Self::is_testnet_mode(env.clone());
-        if !testnet_mode {
-            // Holder concentration guardrail (#26): reject if enforce and over limit
-            let limit_key = DataKey::ConcentrationLimit(issuer.clone(), token.clone());
-            if let Some(config) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, ConcentrationLimitConfig>(&limit_key)
-            {
-                if config.enforce && config.max_bps > 0 {
-                    let curr_key = DataKey::CurrentConcentration(issuer.clone(), token.clone());
-                    let current: u32 = env.storage().persistent().get(&curr_key).unwrap_or(0);
-                    if current > config.max_bps {
-                        return Err(RevoraError::ConcentrationLimitExceeded);
-                    }
-                }
+    /// An offering's summed holder share bps. `InvalidState` when holders exist but the
+    /// total was never recorded, i.e. the shares were written before it was tracked.
+    fn share_total(env: &Env, token: &Address) -> Result<u32, RevoraError> {
+        match env
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalShareBps(token.clone()))
+        {
+            Some(total) => Ok(total),
+            None if Self::get_holder_count(env.clone(), token.clone()) > 0 => {
+                Err(RevoraError::InvalidState)
             }
+            None => Ok(0),
         }
+    }
 
-        let blacklist = Self::get_blacklist(env.clone(), token.clone());
-
-        let key = DataKey::RevenueReports(issuer.clone(), token.clone());
-        let mut reports: Map<u64, (i128, u64)> = env
-            .storage()
+    /// Apply `update` to the stored storage usage counters.
+    fn update_metrics(env: &Env, update: impl FnOnce(&mut StorageMetrics)) {
+        let mut metrics = Self::get_storage_metrics(env.clone());
+        update(&mut metrics);
+        env.storage()
             .persistent()
-            .get(&key)
-            .unwrap_or_else(|| Map::new(&env));
-        let current_timestamp = env.ledger().timestamp();
+            .set(&DataKey::StorageMetrics, &metrics);
+    }
 
-        match reports.get(period_id) {
-            Some((existing_amount, _timestamp)) => {
-                if override_existing {
-                    reports.set(period_id, (amount, current_timestamp));
-                    env.storage().persistent().set(&key, &reports);
+    /// Range checks shared by `reconfigure` and `preview_reconfigure`.
+    fn validate_config_update(env: &Env, update: &ConfigUpdate) -> Result<(), RevoraError> {
+        if update
+            .platform_fee_bps
+            .is_some_and(|bps| bps > MAX_PLATFORM_FEE_BPS)
+        {
+            return Err(RevoraError::InvalidAmount);
+        }
+        if update
+            .max_metadata_len
+            .is_some_and(|len| len == 0 || len > MAX_METADATA_LENGTH_CEILING)
+        {
+            return Err(RevoraError::InvalidAmount);
+        }
+        if update.delinquency_periods == Some(0) {
+            return Err(RevoraError::InvalidAmount);
+        }
+        if update.page_limit.is_some_and(|n| n > PAGE_LIMIT_CEILING)
+            || update
+                .claim_periods
+                .is_some_and(|n| n > CLAIM_PERIODS_CEILING)
+        {
+            return Err(RevoraError::InvalidAmount);
+        }
+        if update.min_claim_delay_secs.is_some() || update.max_claim_delay_secs.is_some() {
+            let (min, max) = Self::claim_delay_bounds(env);
+            let min = update.min_claim_delay_secs.unwrap_or(min);
+            let max = update.max_claim_delay_secs.unwrap_or(max);
+            if min > max {
+                return Err(RevoraError::InvalidAmount);
+            }
+        }
+        Ok(())
+    }
 
-                    env.events().publish(
-                        (EVENT_REVENUE_REPORT_OVERRIDE, issuer.clone(), token.clone()),
-                        (amount, period_id, existing_amount, blacklist.clone()),
-                    );
+    /// Shortest and longest claim delay `set_claim_delay` accepts.
+    fn claim_delay_bounds(env: &Env) -> (u64, u64) {
+        let store = env.storage().persistent();
+        (
+            store.get(&ClaimKey::MinDelaySecs).unwrap_or(0),
+            store
+                .get(&ClaimKey::MaxDelaySecs)
+                .unwrap_or(MAX_CLAIM_DELAY_SECS),
+        )
+    }
 
-                    env.events().publish(
-                        (
-                            EVENT_REVENUE_REPORT_OVERRIDE_ASSET,
-                            issuer.clone(),
-                            token.clone(),
-                            payout_asset.clone(),
-                        ),
-                        (amount, period_id, existing_amount, blacklist.clone()),
-                    );
-                } else {
-                    env.events().publish(
-                        (EVENT_REVENUE_REPORT_REJECTED, issuer.clone(), token.clone()),
-                        (amount, period_id, existing_amount, blacklist.clone()),
-                    );
+    /// Require that `caller` is the stored admin.
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), RevoraError> {
+        let admin = Self::read_admin(env).ok_or(RevoraError::NotInitialized)?;
+        if *caller != admin {
+            return Err(RevoraError::NotAuthorized);
+        }
+        Ok(())
+    }
 
-                    env.events().publish(
-                        (
-                            EVENT_REVENUE_REPORT_REJECTED_ASSET,
-                            issuer.clone(),
-                            token.clone(),
-                            payout_asset.clone(),
-                        ),
-                        (amount, period_id, existing_amount, blacklist.clone()),
-                    );
-                }
-            }
-            None => {
-                reports.set(period_id, (amount, current_timestamp));
-                env.storage().persistent().set(&key, &reports);
+    /// Require that `caller` is the admin or holds `role`.
+    fn require_admin_or_role(env: &Env, caller: &Address, role: Role) -> Result<(), RevoraError> {
+        if Self::has_role(env.clone(), role, caller.clone()) {
+            return Ok(());
+        }
+        Self::require_admin(env, caller)
+    }
 
-                env.events().publish(
-                    (EVENT_REVENUE_REPORT_INITIAL, issuer.clone(), token.clone()),
-                    (amount, period_id, blacklist.clone()),
-                );
+    /// Require that `caller` is one of the safety addresses or holds the `Pauser` role.
+    fn require_safety(env: &Env, caller: &Address) -> Result<(), RevoraError> {
+        if Self::has_role(env.clone(), Role::Pauser, caller.clone()) {
+            return Ok(());
+        }
+        let safety = Self::get_safety_addresses(env.clone());
+        if safety.is_empty() {
+            return Err(RevoraError::NotInitialized);
+        }
+        if !safety.contains(caller) {
+            return Err(RevoraError::NotAuthorized);
+        }
+        Ok(())
+    }
 
-                env.events().publish(
-                    (
-                        EVENT_REVENUE_REPORT_INITIAL_ASSET,
-                        issuer.clone(),
-                        token.clone(),
-                        payout_asset.clone(),
-                    ),
-                    (amount, period_id, blacklist.clone()),
-                );
-            }
+    /// Returns `ContractPaused` if the contract is paused. Used by state-mutating entrypoints.
+    fn require_not_paused(env: &Env) -> Result<(), RevoraError> {
+        if Self::is_paused(env.clone()) {
+            return Err(RevoraError::ContractPaused);
         }
+        Ok(())
+    }
 
-        // Backward-compatible event (preserve `blacklist` for additional publishes)
-        env.events().publish(
-            (EVENT_REVENUE_REPORTED, issuer.clone(), token.clone()),
-            (amount, period_id, blacklist.clone()),
-        );
+    /// Fails with `ContractPaused` under a global pause, `OfferingFrozen` after a failed
+    /// invariant check, or `OfferingPaused` if this offering is paused on its own.
+    ///
+    /// Every offering-scoped mutator calls this except the ones needed while the offering
+    /// is halted: pause controls (`pause_offering`, `pause_period` and their inverses),
+    /// `revoke_claim_delegate`, `flag_delinquent`, `emergency_withdraw`, invariant checks
+    /// (`check_invariants`, `clear_invariant_freeze`), TTL bumps (`bump_offering`,
+    /// `bump_holder`) and admin recovery (`migrate_share_total`, `reset_offering`).
+    /// `calculate_distribution` only emits an event.
+    fn require_offering_not_paused(env: &Env, token: &Address) -> Result<(), RevoraError> {
+        Self::require_not_paused(env)?;
+        if env
+            .storage()
+            .persistent()
+            .has(&OfferingKey::InvariantFrozen(token.clone()))
+        {
+            return Err(RevoraError::OfferingFrozen);
+        }
+        if Self::is_offering_paused(env.clone(), token.clone()) {
+            return Err(RevoraError::OfferingPaused);
+        }
+        Ok(())
+    }
+
+    /// Escrow invariant `period_id` currently violates, if any.
+    fn period_violation(env: &Env, token: &Address, period_id: u64) -> Option<InvariantViolation> {
+        let deposited: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodRevenue(token.clone(), period_id))?;
+        let escrow = Self::get_period_escrow(env.clone(), token.clone(), period_id);
+        if escrow.distributed > deposited {
+            Some(InvariantViolation::ClaimedExceedsDeposit)
+        } else if deposited - escrow.distributed - escrow.swept < 0 {
+            Some(InvariantViolation::NegativeLiability)
+        } else {
+            None
+        }
+    }
 
+    /// Record the violation and emit a diagnostic event. Callers must return `Ok` so the
+    /// freeze is not rolled back with the rest of the call.
+    fn freeze_offering_on(
+        env: &Env,
+        token: &Address,
+        violation: InvariantViolation,
+        period_id: Option<u64>,
+    ) {
+        env.storage()
+            .persistent()
+            .set(&OfferingKey::InvariantFrozen(token.clone()), &violation);
         env.events().publish(
-            (
-                EVENT_REVENUE_REPORTED_ASSET,
-                issuer.clone(),
-                token.clone(),
-                payout_asset.clone(),
-            ),
-            (amount, period_id),
+            (EVENT_INVARIANT_FROZEN, token.clone()),
+            (violation, period_id),
         );
+    }
 
-        // Optionally emit versioned v1 events for forward-compatible consumers
-        if Self::is_event_versioning_enabled(env.clone()) {
-            env.events().publish(
-                (EVENT_REV_INIT_V1, issuer.clone(), token.clone()),
-                (EVENT_SCHEMA_VERSION, amount, period_id, blacklist.clone()),
-            );
-
-            env.events().publish(
-                (
-                    EVENT_REV_INIA_V1,
-                    issuer.clone(),
-                    token.clone(),
-                    payout_asset.clone(),
-                ),
-                (EVENT_SCHEMA_VERSION, amount, period_id, blacklist.clone()),
-            );
-
-            env.events().publish(
-                (EVENT_REV_REP_V1, issuer.clone(), token.clone()),
-                (EVENT_SCHEMA_VERSION, amount, period_id, blacklist.clone()),
-            );
-
-            env.events().publish(
-                (
-                    EVENT_REV_REPA_V1,
-                    issuer.clone(),
-                    token.clone(),
-                    payout_asset.clone(),
-                ),
-                (EVENT_SCHEMA_VERSION, amount, period_id),
-            );
+    fn require_offering_pauser(
+        env: &Env,
+        caller: &Address,
+        token: &Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(env)?;
+        caller.require_auth();
+        let issuer = Self::get_current_issuer(env, token).ok_or(RevoraError::OfferingNotFound)?;
+        if *caller != issuer {
+            Self::require_admin_or_role(env, caller, Role::Pauser)?;
         }
+        Ok(())
+    }
 
-        // Audit log summary (#34): maintain per-offering total revenue and report count
-        let summary_key = DataKey::AuditSummary(issuer.clone(), token.clone());
-        let mut summary: AuditSummary =
-            env.storage()
-                .persistent()
-                .get(&summary_key)
-                .unwrap_or(AuditSummary {
-                    total_revenue: 0,
-                    report_count: 0,
-                });
-        summary.total_revenue = summary.total_revenue.saturating_add(amount);
-        summary.report_count = summary.report_count.saturating_add(1);
-        env.storage().persistent().set(&summary_key, &summary);
-
+    /// Fail with `PeriodOffSchedule` when `token` has a schedule and `period_id` is 0 or
+    /// has not started yet.
+    fn require_on_schedule(env: &Env, token: &Address, period_id: u64) -> Result<(), RevoraError> {
+        if Self::get_period_schedule(env.clone(), token.clone()).is_none() {
+            return Ok(());
+        }
+        let current =
+            Self::get_expected_period(env.clone(), token.clone(), env.ledger().timestamp())
+                .unwrap_or(0);
+        if period_id == 0 || period_id > current {
+            return Err(RevoraError::PeriodOffSchedule);
+        }
         Ok(())
     }
 
-    /// Return the total number of offerings registered by `issuer`.
-    pub fn get_offering_count(env: Env, issuer: Address) -> u32 {
-        let count_key = DataKey::OfferCount(issuer);
-        env.storage().persistent().get(&count_key).unwrap_or(0)
+    /// Cross-contract check that `token` is a live token: it must answer `decimals`
+    /// (`OfferingTokenUnavailable` otherwise), and a `total_supply` it reports must be
+    /// positive (`ZeroTotalSupply`). SEP-41 has no supply getter, so tokens without one,
+    /// such as Stellar asset contracts, pass on `decimals` alone.
+    fn verify_offering_token(env: &Env, token: &Address) -> Result<(), RevoraError> {
+        if !matches!(token::Client::new(env, token).try_decimals(), Ok(Ok(_))) {
+            return Err(RevoraError::OfferingTokenUnavailable);
+        }
+        let supply = env.try_invoke_contract::<i128, soroban_sdk::Error>(
+            token,
+            &Symbol::new(env, "total_supply"),
+            Vec::new(env),
+        );
+        if matches!(supply, Ok(Ok(total)) if total <= 0) {
+            return Err(RevoraError::ZeroTotalSupply);
+        }
+        Ok(())
     }
 
-    /// Return a page of offerings for `issuer`. Limit capped at MAX_PAGE_LIMIT (20).
-    pub fn get_offerings_page(
-        env: Env,
-        issuer: Address,
-        start: u32,
-        limit: u32,
-    ) -> (Vec<Offering>, Option<u32>) {
+    /// Position of `token` in `issuer`'s offering list. Reads `OfferingKey::ByToken`
+    /// directly and only scans the list for offerings registered before that index
+    /// existed (or a token registered twice).
+    fn find_offering_index(env: &Env, issuer: &Address, token: &Address) -> Option<u32> {
+        let store = env.storage().persistent();
+        if let Some(index) = store.get::<OfferingKey, u32>(&OfferingKey::ByToken(token.clone())) {
+            let item: Option<Offering> = store.get(&DataKey::OfferItem(issuer.clone(), index));
+            if item.map(|o| o.token == *token).unwrap_or(false) {
+                return Some(index);
+            }
+        }
         let count = Self::get_offering_count(env.clone(), issuer.clone());
+        (0..count).find(|&i| {
+            let offering: Offering = store.get(&DataKey::OfferItem(issuer.clone(), i)).unwrap();
+            offering.token == *token
+        })
+    }
 
-        let effective_limit = if limit == 0 || limit > MAX_PAGE_LIMIT {
-            MAX_PAGE_LIMIT
-        } else {
-            limit
+    fn require_metadata_fits(env: &Env, metadata: &OfferingMetadata) -> Result<(), RevoraError> {
+        let len = match metadata {
+            OfferingMetadata::Text(text) => text.len(),
+            OfferingMetadata::Bytes(bytes) => bytes.len(),
         };
-
-        if start >= count {
-            return (Vec::new(&env), None);
+        if len > Self::get_config(env.clone()).max_metadata_len {
+            return Err(RevoraError::MetadataTooLarge);
         }
+        Ok(())
+    }
 
-        let end = core::cmp::min(start + effective_limit, count);
-        let mut results = Vec::new(&env);
-
-        for i in start..end {
-            let item_key = DataKey::OfferItem(issuer.clone(), i);
-            let offering: Offering = env.storage().persistent().get(&item_key).unwrap();
-            results.push_back(offering);
+    /// Blacklist edits are open to any caller until the offering names a compliance
+    /// officer; from then on only the issuer, the officer and `ComplianceOfficer` role
+    /// holders qualify.
+    fn require_blacklist_manager(
+        env: &Env,
+        caller: &Address,
+        token: &Address,
+    ) -> Result<(), RevoraError> {
+        let officer = match Self::get_compliance_officer(env.clone(), token.clone()) {
+            Some(officer) => officer,
+            None => return Ok(()),
+        };
+        if *caller == officer
+            || Self::get_current_issuer(env, token).as_ref() == Some(caller)
+            || Self::has_role(env.clone(), Role::ComplianceOfficer, caller.clone())
+        {
+            return Ok(());
         }
-
-        let next_cursor = if end < count { Some(end) } else { None };
-        (results, next_cursor)
+        Err(RevoraError::NotAuthorized)
     }
 
-    /// Add `investor` to the per-offering blacklist for `token`. Idempotent.
-    pub fn blacklist_add(
+    fn edit_whitelist_bulk(
         env: Env,
         caller: Address,
         token: Address,
-        investor: Address,
-    ) -> Result<(), RevoraError> {
+        investors: Vec<Address>,
+        add: bool,
+    ) -> Result<u32, RevoraError> {
         Self::require_not_frozen(&env)?;
-        Self::require_not_paused(&env);
+        Self::require_offering_not_paused(&env, &token)?;
         caller.require_auth();
+        Self::require_whitelist_manager(&env, &caller, &token)?;
+        if investors.len() > MAX_LIST_BATCH {
+            return Err(RevoraError::LimitReached);
+        }
 
-        let key = DataKey::Blacklist(token.clone());
+        let key = ComplianceKey::Whitelist(token.clone());
         let mut map: Map<Address, bool> = env
             .storage()
             .persistent()
             .get(&key)
             .unwrap_or_else(|| Map::new(&env));
-
-        map.set(investor.clone(), true);
+        let mut changed: u32 = 0;
+        for investor in investors.iter() {
+            if add {
+                if !map.contains_key(investor.clone()) {
+                    changed += 1;
+                }
+                map.set(investor, true);
+            } else if map.remove(investor).is_some() {
+                changed += 1;
+            }
+        }
         env.storage().persistent().set(&key, &map);
 
+        let event = if add {
+            EVENT_WL_ADD_BULK
+        } else {
+            EVENT_WL_REM_BULK
+        };
         env.events()
-            .publish((EVENT_BL_ADD, token, caller), investor);
-        Ok(())
+            .publish((event, token, caller), (investors.len(), changed));
+        Ok(changed)
     }
 
-    /// Remove `investor` from the per-offering blacklist for `token`. Idempotent.
-    pub fn blacklist_remove(
-        env: Env,
-        caller: Address,
-        token: Address,
-        investor: Address,
-    ) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
-        Self::require_not_paused(&env);
-        caller.require_auth();
-
-        let key = DataKey::Blacklist(token.clone());
-        let mut map: Map<Address, bool> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| Map::new(&env));
-
-        map.remove(investor.clone());
-        env.storage().persistent().set(&key, &map);
+    /// Whether `investor` is barred from `token`: globally or by the offering's blacklist.
+    fn is_barred(env: &Env, token: &Address, investor: &Address) -> bool {
+        Self::is_globally_blacklisted(env.clone(), investor.clone())
+            || Self::is_blacklisted(env.clone(), token.clone(), investor.clone())
+    }
 
-        env.events()
-            .publish((EVENT_BL_REM, token, caller), investor);
+    /// Fail with `HolderLocked` while `holder`'s claims on `token` are held.
+    fn require_not_locked(env: &Env, token: &Address, holder: &Address) -> Result<(), RevoraError> {
+        if env.ledger().timestamp()
+            < Self::get_holder_lockup(env.clone(), token.clone(), holder.clone())
+        {
+            return Err(RevoraError::HolderLocked);
+        }
         Ok(())
     }
 
-    /// Returns `true` if `investor` is blacklisted for `token`'s offering.
-    pub fn is_blacklisted(env: Env, token: Address, investor: Address) -> bool {
-        let key = DataKey::Blacklist(token);
-        env.storage()
-            .persistent()
-            .get::<DataKey, Map<Address, bool>>(&key)
-            .map(|m| m.get(investor).unwrap_or(false))
-            .unwrap_or(false)
+    /// Whether the registry attests `holder`. Fails closed: no registry, or a registry
+    /// call that errors, counts as not attested.
+    fn has_kyc_attestation(env: &Env, holder: &Address) -> bool {
+        let Some(registry) = Self::get_kyc_registry(env.clone()) else {
+            return false;
+        };
+        let args: Vec<Val> = (holder.clone(),).into_val(env);
+        matches!(
+            env.try_invoke_contract::<bool, soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(env, "has_attestation"),
+                args,
+            ),
+            Ok(Ok(true))
+        )
     }
 
-    /// Return all blacklisted addresses for `token`'s offering.
-    pub fn get_blacklist(env: Env, token: Address) -> Vec<Address> {
-        let key = DataKey::Blacklist(token);
-        env.storage()
-            .persistent()
-            .get::<DataKey, Map<Address, bool>>(&key)
-            .map(|m| m.keys())
-            .unwrap_or_else(|| Vec::new(&env))
+    /// Fail with `KycAttestationMissing` when `token` requires KYC and `holder` is not
+    /// attested.
+    fn require_kyc(env: &Env, token: &Address, holder: &Address) -> Result<(), RevoraError> {
+        if Self::is_kyc_required(env.clone(), token.clone())
+            && !Self::has_kyc_attestation(env, holder)
+        {
+            return Err(RevoraError::KycAttestationMissing);
+        }
+        Ok(())
     }
 
-    // ── Holder concentration guardrail (#26) ───────────────────
-
-    /// Set per-offering concentration limit. Caller must be the offering issuer.
-    /// `max_bps`: max allowed single-holder share in basis points (0 = disable).
-    /// `enforce`: if true, report_revenue will fail when reported concentration exceeds max_bps.
-    pub fn set_concentration_limit(
-        env: Env,
-        issuer: Address,
-        token: Address,
-        max_bps: u32,
-        enforce: bool,
+    /// Fail with `RestrictionViolated` when `token` has restrictions that `holder`'s
+    /// profile (or lack of one) does not meet.
+    fn require_restrictions_met(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
     ) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
-
-        // Verify offering exists and issuer is current
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::LimitReached)?;
-
-        if current_issuer != issuer {
-            return Err(RevoraError::LimitReached);
+        let Some(restrictions) = Self::get_offering_restrictions(env.clone(), token.clone()) else {
+            return Ok(());
+        };
+        let Some(profile) = Self::get_holder_profile(env.clone(), token.clone(), holder.clone())
+        else {
+            return Err(RevoraError::RestrictionViolated);
+        };
+        if restrictions.accredited_only && !profile.accredited {
+            return Err(RevoraError::RestrictionViolated);
+        }
+        if !restrictions.allowed_jurisdictions.is_empty()
+            && !restrictions
+                .allowed_jurisdictions
+                .contains(&profile.jurisdiction)
+        {
+            return Err(RevoraError::RestrictionViolated);
         }
-
-        issuer.require_auth();
-        let key = DataKey::ConcentrationLimit(issuer, token);
-        env.storage()
-            .persistent()
-            .set(&key, &ConcentrationLimitConfig { max_bps, enforce });
         Ok(())
     }
 
-    /// Report current top-holder concentration in bps. Emits warning event if over configured limit.
-    pub fn report_concentration(
-        env: Env,
-        issuer: Address,
-        token: Address,
-        concentration_bps: u32,
+    /// Fail with `TermsNotAcknowledged` when `token` requires terms acknowledgement and
+    /// `holder`'s latest acknowledgement is not for the current terms hash.
+    fn require_terms_acknowledged(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
     ) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
+        let Some(terms) = Self::get_offering_terms(env.clone(), token.clone()) else {
+            return Ok(());
+        };
+        if !terms.required {
+            return Ok(());
+        }
+        match Self::get_terms_acknowledgement(env.clone(), token.clone(), holder.clone()) {
+            Some(ack) if ack.hash == terms.hash => Ok(()),
+            _ => Err(RevoraError::TermsNotAcknowledged),
+        }
+    }
 
-        // Verify offering exists and issuer is current
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+    /// Fail with `HolderNotWhitelisted` when `token` enforces its whitelist and `holder`
+    /// is not on it.
+    fn require_whitelisted(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+    ) -> Result<(), RevoraError> {
+        if Self::is_whitelist_enabled(env.clone(), token.clone())
+            && !Self::is_whitelisted(env.clone(), token.clone(), holder.clone())
+        {
+            return Err(RevoraError::HolderNotWhitelisted);
+        }
+        Ok(())
+    }
 
-        if current_issuer != issuer {
-            return Err(RevoraError::OfferingNotFound);
+    /// Whitelist edits are limited to the issuer, the offering's compliance officer and
+    /// `ComplianceOfficer` role holders.
+    fn require_whitelist_manager(
+        env: &Env,
+        caller: &Address,
+        token: &Address,
+    ) -> Result<(), RevoraError> {
+        let issuer = Self::get_current_issuer(env, token).ok_or(RevoraError::OfferingNotFound)?;
+        if *caller == issuer
+            || Self::get_compliance_officer(env.clone(), token.clone()).as_ref() == Some(caller)
+            || Self::has_role(env.clone(), Role::ComplianceOfficer, caller.clone())
+        {
+            return Ok(());
         }
+        Err(RevoraError::NotAuthorized)
+    }
 
-        issuer.require_auth();
+    /// Store current concentration and emit a warning if over the configured limit.
+    fn store_concentration(env: &Env, issuer: Address, token: Address, concentration_bps: u32) {
         let curr_key = DataKey::CurrentConcentration(issuer.clone(), token.clone());
         env.storage()
             .persistent()
             .set(&curr_key, &concentration_bps);
+        if concentration_bps > 10_000 {
+            Self::freeze_offering_on(env, &token, InvariantViolation::ConcentrationOverflow, None);
+        }
 
         let limit_key = DataKey::ConcentrationLimit(issuer.clone(), token.clone());
         if let Some(config) = env
@@ -757,243 +2174,151 @@ impl RevoraRevenueShare {
                 );
             }
         }
-        Ok(())
     }
 
-    /// Get concentration limit config for an offering.
-    pub fn get_concentration_limit(
-        env: Env,
-        issuer: Address,
-        token: Address,
-    ) -> Option<ConcentrationLimitConfig> {
-        let key = DataKey::ConcentrationLimit(issuer, token);
-        env.storage().persistent().get(&key)
-    }
-
-    /// Get last reported concentration in bps for an offering.
-    pub fn get_current_concentration(env: Env, issuer: Address, token: Address) -> Option<u32> {
-        let key = DataKey::CurrentConcentration(issuer, token);
-        env.storage().persistent().get(&key)
-    }
-
-    // ── Audit log summary (#34) ────────────────────────────────
-
-    /// Get per-offering audit summary (total revenue and report count).
-    pub fn get_audit_summary(env: Env, issuer: Address, token: Address) -> Option<AuditSummary> {
-        let key = DataKey::AuditSummary(issuer, token);
-        env.storage().persistent().get(&key)
-    }
-
-    // ── Configurable rounding (#44) ───────────────────────────
-
-    /// Set rounding mode for an offering's share calculations. Caller must be issuer.
-    pub fn set_rounding_mode(
-        env: Env,
-        issuer: Address,
-        token: Address,
-        mode: RoundingMode,
-    ) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
-
-        // Verify offering exists and issuer is current
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::LimitReached)?;
-
-        if current_issuer != issuer {
-            return Err(RevoraError::LimitReached);
+    /// Place `period_id` in the period list so claims walk periods in `period_id` order. Entries
+    /// at or below the claim watermark are never moved, and at most `MAX_PERIOD_REORDER`
+    /// entries are shifted; a period older than either bound lands just above it.
+    fn insert_period_entry(env: &Env, token: &Address, period_id: u64, count: u32) {
+        let store = env.storage().persistent();
+        let watermark: u32 = store
+            .get(&ClaimKey::ClaimWatermark(token.clone()))
+            .unwrap_or(0);
+        let floor = core::cmp::max(watermark, count.saturating_sub(MAX_PERIOD_REORDER));
+        let mut pos = count;
+        while pos > floor {
+            let prev: u64 = store
+                .get(&DataKey::PeriodEntry(token.clone(), pos - 1))
+                .unwrap();
+            if prev < period_id {
+                break;
+            }
+            store.set(&DataKey::PeriodEntry(token.clone(), pos), &prev);
+            store.set(&DataKey::PeriodIndex(token.clone(), prev), &pos);
+            pos -= 1;
         }
-
-        issuer.require_auth();
-        let key = DataKey::RoundingMode(issuer, token);
-        env.storage().persistent().set(&key, &mode);
-        Ok(())
-    }
-
-    /// Get rounding mode for an offering. Defaults to Truncation if not set.
-    pub fn get_rounding_mode(env: Env, issuer: Address, token: Address) -> RoundingMode {
-        let key = DataKey::RoundingMode(issuer, token);
-        env.storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(RoundingMode::Truncation)
+        store.set(&DataKey::PeriodEntry(token.clone(), pos), &period_id);
+        store.set(&DataKey::PeriodIndex(token.clone(), period_id), &pos);
     }
 
-    /// Compute share of `amount` at `revenue_share_bps` using the given rounding mode.
-    /// Guarantees: result between 0 and amount (inclusive); no loss of funds when summing shares if caller uses same mode.
-    pub fn compute_share(
-        _env: Env,
-        amount: i128,
-        revenue_share_bps: u32,
-        mode: RoundingMode,
-    ) -> i128 {
-        if revenue_share_bps > 10_000 {
-            return 0;
+    /// Raise the claim watermark to `idx` if a holder's cursor moved past it.
+    fn raise_claim_watermark(env: &Env, token: &Address, idx: u32) {
+        let key = ClaimKey::ClaimWatermark(token.clone());
+        let watermark: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        if idx > watermark {
+            env.storage().persistent().set(&key, &idx);
         }
-        let bps = revenue_share_bps as i128;
-        let raw = amount.checked_mul(bps).unwrap_or(0);
-        let share = match mode {
-            RoundingMode::Truncation => raw.checked_div(10_000).unwrap_or(0),
-            RoundingMode::RoundHalfUp => {
-                let half = 5_000_i128;
-                let adjusted = if raw >= 0 {
-                    raw.saturating_add(half)
-                } else {
-                    raw.saturating_sub(half)
-                };
-                adjusted.checked_div(10_000).unwrap_or(0)
-            }
-        };
-        // Clamp to [min(0, amount), max(0, amount)] to avoid overflow semantics affecting bounds
-        let lo = core::cmp::min(0, amount);
-        let hi = core::cmp::max(0, amount);
-        core::cmp::min(core::cmp::max(share, lo), hi)
     }
 
-    // ── Multi-period aggregated claims ───────────────────────────
-
-    /// Deposit revenue for a specific period of an offering.
-    ///
-    /// Transfers `amount` of `payment_token` from `issuer` to the contract.
-    /// The payment token is locked per offering on first deposit; subsequent
-    /// deposits must use the same payment token.
-    pub fn deposit_revenue(
-        env: Env,
-        issuer: Address,
-        token: Address,
-        payment_token: Address,
-        amount: i128,
-        period_id: u64,
-    ) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
-
-        // Verify offering exists and issuer is current
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
-
-        if current_issuer != issuer {
-            return Err(RevoraError::OfferingNotFound);
+    /// Upper bound for summed holder shares: the offering's revenue_share_bps, or 10000
+    /// in testnet mode (where registration also skips bps validation).
+    fn allocation_cap(env: &Env, issuer: &Address, token: &Address) -> u32 {
+        if Self::is_testnet_mode(env.clone()) {
+            return 10_000;
         }
+        Self::get_offering(env.clone(), issuer.clone(), token.clone())
+            .map(|o| o.revenue_share_bps)
+            .unwrap_or(0)
+    }
 
-        // Verify offering exists
-        let offering = Self::get_offering(env.clone(), issuer.clone(), token.clone())
-            .ok_or(RevoraError::OfferingNotFound)?;
-        if offering.payout_asset != payment_token {
-            return Err(RevoraError::PayoutAssetMismatch);
+    /// Reinvestment for a claim paying `available` to `holder`: the issuer, the amount
+    /// spent and the bps bought. `None` when the holder has not opted in, no rate is set,
+    /// the offering is closed, its share total awaits migration, or nothing can be bought.
+    fn reinvest_plan(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        available: i128,
+    ) -> Option<(Address, i128, u32)> {
+        if !Self::is_auto_reinvest(env.clone(), token.clone(), holder.clone()) {
+            return None;
         }
-
-        issuer.require_auth();
-
-        // Check period not already deposited
-        let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
-        if env.storage().persistent().has(&rev_key) {
-            return Err(RevoraError::PeriodAlreadyDeposited);
+        let rate = Self::get_reinvest_rate(env.clone(), token.clone());
+        if rate == 0 || Self::require_offering_open(env, token).is_err() {
+            return None;
         }
-
-        // Store or validate payment token for this offering
-        let pt_key = DataKey::PaymentToken(token.clone());
-        if let Some(existing_pt) = env.storage().persistent().get::<DataKey, Address>(&pt_key) {
-            if existing_pt != payment_token {
-                return Err(RevoraError::PaymentTokenMismatch);
-            }
-        } else {
-            env.storage().persistent().set(&pt_key, &payment_token);
+        let issuer = Self::get_current_issuer(env, token)?;
+        let total = Self::share_total(env, token).ok()?;
+        let room = Self::allocation_cap(env, &issuer, token).saturating_sub(total);
+        let bps = core::cmp::min(available / rate, room as i128) as u32;
+        if bps == 0 {
+            return None;
         }
-
-        // Transfer tokens from issuer to contract
-        let contract_addr = env.current_contract_address();
-        token::Client::new(&env, &payment_token).transfer(&issuer, &contract_addr, &amount);
-
-        // Store period revenue
-        env.storage().persistent().set(&rev_key, &amount);
-
-        // Store deposit timestamp for time-delayed claims (#27)
-        let deposit_time = env.ledger().timestamp();
-        let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
-        env.storage().persistent().set(&time_key, &deposit_time);
-
-        // Append to indexed period list
-        let count_key = DataKey::PeriodCount(token.clone());
-        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-        let entry_key = DataKey::PeriodEntry(token.clone(), count);
-        env.storage().persistent().set(&entry_key, &period_id);
-        env.storage().persistent().set(&count_key, &(count + 1));
-
-        env.events().publish(
-            (EVENT_REV_DEPOSIT, issuer, token),
-            (payment_token, amount, period_id),
-        );
-        Ok(())
+        Some((issuer, bps as i128 * rate, bps))
     }
 
-    /// Set a holder's revenue share (in basis points) for an offering.
-    ///
-    /// Only the offering issuer may call this. `share_bps` must be <= 10000.
-    pub fn set_holder_share(
-        env: Env,
-        issuer: Address,
-        token: Address,
-        holder: Address,
-        share_bps: u32,
-    ) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
-
-        // Verify offering exists and issuer is current
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
-
-        if current_issuer != issuer {
-            return Err(RevoraError::OfferingNotFound);
-        }
-
-        issuer.require_auth();
-
-        if share_bps > 10_000 {
-            return Err(RevoraError::InvalidShareBps);
+    fn allocate_sub_accounts(
+        env: &Env,
+        accounts: &Vec<SubAccount>,
+        amount: i128,
+    ) -> Vec<(u64, i128)> {
+        let mut parts = Vec::new(env);
+        let mut remaining = amount;
+        for (i, account) in accounts.iter().enumerate() {
+            let part = if i as u32 + 1 == accounts.len() {
+                remaining
+            } else {
+                math::bps_of(amount, account.bps)
+            };
+            remaining -= part;
+            parts.push_back((account.id, part));
         }
+        parts
+    }
 
-        let key = DataKey::HolderShare(token.clone(), holder.clone());
-        env.storage().persistent().set(&key, &share_bps);
-
-        env.events()
-            .publish((EVENT_SHARE_SET, issuer, token), (holder, share_bps));
-        Ok(())
+    /// Walk for `holder`'s next claim on `token`, reading the settled band when newest first.
+    fn claim_walk(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+        start: u32,
+        count: u32,
+    ) -> ClaimWalk {
+        let newest = Self::get_holder_claim_order(env.clone(), token.clone(), holder.clone())
+            == ClaimOrder::NewestFirst;
+        let band = if newest {
+            env.storage()
+                .persistent()
+                .get(&ClaimKey::SettledBand(token.clone(), holder.clone()))
+        } else {
+            None
+        };
+        ClaimWalk::new(newest, start, count, band)
     }
 
-    /// Return a holder's share in basis points for an offering (0 if unset).
-    pub fn get_holder_share(env: Env, token: Address, holder: Address) -> u32 {
-        let key = DataKey::HolderShare(token, holder);
-        env.storage().persistent().get(&key).unwrap_or(0)
+    /// Where the holder's payouts go: their payout address if set, else the holder.
+    fn payout_recipient(env: &Env, token: &Address, holder: &Address) -> Address {
+        Self::get_payout_address(env.clone(), token.clone(), holder.clone())
+            .unwrap_or_else(|| holder.clone())
     }
 
-    /// Claim aggregated revenue across multiple unclaimed periods.
-    ///
-    /// `max_periods` controls how many periods to process in one call
-    /// (0 = up to MAX_CLAIM_PERIODS). Returns the total payout amount.
-    ///
-    /// Aggregation semantics:
-    /// - Periods are processed in deposit order (sequential index).
-    /// - Each holder's payout per period = `period_revenue * share_bps / 10000`.
-    /// - The holder's claim index advances regardless of zero-value periods.
-    /// - Capped at MAX_CLAIM_PERIODS (50) per transaction for gas safety.
-    pub fn claim(
+    fn do_claim(
         env: Env,
         holder: Address,
         token: Address,
+        recipient: Address,
         max_periods: u32,
-    ) -> Result<i128, RevoraError> {
-        holder.require_auth();
-
-        if Self::is_blacklisted(env.clone(), token.clone(), holder.clone()) {
+    ) -> Result<ClaimResult, RevoraError> {
+        Self::require_offering_not_paused(&env, &token)?;
+        if Self::is_barred(&env, &token, &holder) {
             return Err(RevoraError::HolderBlacklisted);
         }
+        Self::require_not_locked(&env, &token, &holder)?;
+        Self::require_kyc(&env, &token, &holder)?;
+        Self::require_restrictions_met(&env, &token, &holder)?;
+        Self::require_whitelisted(&env, &token, &holder)?;
 
         let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
-        if share_bps == 0 {
-            return Err(RevoraError::NoPendingClaims);
+        let balance = Self::live_balance(&env, &token, &holder);
+        if share_bps == 0 && balance.unwrap_or(0) == 0 {
+            return Err(RevoraError::NoShare);
         }
 
         let count_key = DataKey::PeriodCount(token.clone());
         let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        if period_count == 0 {
+            return Err(RevoraError::NothingDeposited);
+        }
 
         let idx_key = DataKey::LastClaimedIdx(token.clone(), holder.clone());
         let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
@@ -1002,86 +2327,266 @@ impl RevoraRevenueShare {
             return Err(RevoraError::NoPendingClaims);
         }
 
-        let effective_max = if max_periods == 0 || max_periods > MAX_CLAIM_PERIODS {
-            MAX_CLAIM_PERIODS
+        let claim_cap = Self::get_network_limits(env.clone()).claim_periods;
+        let effective_max = if max_periods == 0 || max_periods > claim_cap {
+            claim_cap
         } else {
             max_periods
         };
-        let end_idx = core::cmp::min(start_idx + effective_max, period_count);
+        let mut walk = Self::claim_walk(&env, &token, &holder, start_idx, period_count);
+        let steps = core::cmp::min(walk.len(), effective_max);
 
-        let delay_key = DataKey::ClaimDelaySecs(token.clone());
-        let delay_secs: u64 = env.storage().persistent().get(&delay_key).unwrap_or(0);
+        let delays = Self::claim_delay_schedule(&env, &token);
+        let bonus = Self::get_claim_bonus(env.clone(), token.clone());
+        let stream_secs = Self::get_stream_duration(env.clone(), token.clone());
         let now = env.ledger().timestamp();
 
         let mut total_payout: i128 = 0;
+        let mut bonus_due: i128 = 0;
         let mut claimed_periods = Vec::new(&env);
+        let mut period_payouts = Vec::new(&env);
         let mut last_claimed_idx = start_idx;
+        let mut cursor_open = true;
+        let mut blocked_by_pause = false;
+        let mut violation = None;
 
-        for i in start_idx..end_idx {
+        for k in 0..steps {
+            let i = walk.index(k);
             let entry_key = DataKey::PeriodEntry(token.clone(), i);
             let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
             let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
             let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
-            if delay_secs > 0 && now < deposit_time.saturating_add(delay_secs) {
-                break;
+            // Newest first skips periods it cannot settle yet and carries on with older ones.
+            if Self::in_claim_delay(&env, &delays, deposit_time) {
+                if !walk.newest {
+                    break;
+                }
+                walk.visit(i, false);
+                continue;
             }
-            let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
-            let revenue: i128 = env.storage().persistent().get(&rev_key).unwrap();
-            let payout = revenue * (share_bps as i128) / 10_000;
+            if Self::is_period_paused(env.clone(), token.clone(), period_id) {
+                blocked_by_pause = true;
+                if !walk.newest {
+                    break;
+                }
+                walk.visit(i, false);
+                continue;
+            }
+            let elapsed = now.saturating_sub(deposit_time);
+            let (payout, unlocked) = Self::streamed_payout(
+                &env,
+                &token,
+                period_id,
+                &holder,
+                share_bps,
+                balance,
+                elapsed,
+                stream_secs,
+            );
+            if payout > 0 {
+                let revenue: i128 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+                    .unwrap_or(0);
+                let distributed = Self::period_distributed(&env, &token, period_id);
+                if distributed + payout > revenue {
+                    violation = Some((InvariantViolation::ClaimedExceedsDeposit, period_id));
+                    break;
+                }
+            }
+            Self::record_distributed(&env, &token, period_id, payout);
+            Self::extend_period_ttl(&env, &token, i, period_id);
+            bonus_due += Self::prompt_bonus(&bonus, payout, deposit_time, now);
             total_payout += payout;
-            claimed_periods.push_back(period_id);
-            last_claimed_idx = i + 1;
+            // The cursor stops at the first period still streaming; anything paid beyond it
+            // is recorded like a push so the next claim pays only the difference. Newest
+            // first always pays beyond it.
+            cursor_open &= unlocked && !walk.newest;
+            walk.visit(i, unlocked);
+            if cursor_open {
+                last_claimed_idx = i + 1;
+            } else if payout > 0 {
+                let paid_key = DataKey::PushPaid(token.clone(), period_id, holder.clone());
+                let paid: i128 = env.storage().persistent().get(&paid_key).unwrap_or(0);
+                env.storage().persistent().set(&paid_key, &(paid + payout));
+            }
+            if cursor_open || payout > 0 || (walk.newest && unlocked) {
+                claimed_periods.push_back(period_id);
+                period_payouts.push_back(payout);
+            }
         }
+        let (last_claimed_idx, band) = if walk.newest {
+            walk.finish()
+        } else {
+            (last_claimed_idx, None)
+        };
+        let progressed = last_claimed_idx > start_idx || (walk.newest && walk.run.is_some());
 
-        if last_claimed_idx == start_idx {
+        // Pay the periods before the anomaly and freeze the offering; returning `Ok` keeps
+        // the freeze from being rolled back.
+        if let Some((v, period_id)) = violation {
+            Self::freeze_offering_on(&env, &token, v, Some(period_id));
+        }
+        if !progressed && total_payout == 0 {
+            if violation.is_some() {
+                return Ok(ClaimResult {
+                    amount: 0,
+                    periods_claimed: 0,
+                    more_pending: true,
+                });
+            }
+            if blocked_by_pause {
+                return Err(RevoraError::PeriodPaused);
+            }
             return Err(RevoraError::ClaimDelayNotElapsed);
         }
 
+        // Prompt-claim bonus, limited to what is left in the pool.
+        let mut bonus_paid: i128 = 0;
+        if bonus_due > 0 {
+            let pool_key = ClaimKey::BonusPool(token.clone());
+            let pool: i128 = env.storage().persistent().get(&pool_key).unwrap_or(0);
+            bonus_paid = core::cmp::min(bonus_due, pool);
+            if bonus_paid > 0 {
+                env.storage()
+                    .persistent()
+                    .set(&pool_key, &(pool - bonus_paid));
+                env.events().publish(
+                    (EVENT_BONUS_PAID, holder.clone(), token.clone()),
+                    bonus_paid,
+                );
+            }
+        }
+        let amount = total_payout + bonus_paid;
+
         // Transfer only if there is a positive payout
-        if total_payout > 0 {
+        let mut donated: i128 = 0;
+        if amount > 0 {
             let pt_key = DataKey::PaymentToken(token.clone());
             let payment_token: Address = env.storage().persistent().get(&pt_key).unwrap();
             let contract_addr = env.current_contract_address();
-            token::Client::new(&env, &payment_token).transfer(
-                &contract_addr,
-                &holder,
-                &total_payout,
-            );
+            let client = token::Client::new(&env, &payment_token);
+            if let Some(split) = Self::get_donation(env.clone(), token.clone(), holder.clone()) {
+                donated = math::bps_of(amount, split.bps);
+                if donated > 0 {
+                    client.transfer(&contract_addr, &split.recipient, &donated);
+                    env.events().publish(
+                        (EVENT_DONATED, holder.clone(), token.clone()),
+                        (split.recipient, donated),
+                    );
+                }
+            }
+            // Reinvest only once every period is settled: a larger share must not reach
+            // back into periods the holder has yet to claim.
+            let mut reinvested: i128 = 0;
+            let settled = last_claimed_idx == period_count && band.is_none();
+            if let Some((issuer, spent, bps)) = settled
+                .then(|| Self::reinvest_plan(&env, &token, &holder, amount - donated))
+                .flatten()
+            {
+                let share = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+                Self::write_holder_share(&env, &token, &holder, share + bps)?;
+                client.transfer(&contract_addr, &issuer, &spent);
+                env.events().publish(
+                    (EVENT_REINVESTED, holder.clone(), token.clone()),
+                    (spent, bps, share + bps),
+                );
+                reinvested = spent;
+            }
+            if amount > donated + reinvested {
+                client.transfer(&contract_addr, &recipient, &(amount - donated - reinvested));
+            }
+            let accounts = Self::get_sub_accounts(env.clone(), token.clone(), holder.clone());
+            if !accounts.is_empty() {
+                env.events().publish(
+                    (EVENT_SUB_ALLOCATED, holder.clone(), token.clone()),
+                    Self::allocate_sub_accounts(&env, &accounts, amount - donated),
+                );
+            }
         }
 
         // Advance claim index only for periods actually claimed (respecting delay)
         env.storage().persistent().set(&idx_key, &last_claimed_idx);
+        if walk.newest {
+            let band_key = ClaimKey::SettledBand(token.clone(), holder.clone());
+            match band {
+                Some(band) => env.storage().persistent().set(&band_key, &band),
+                None => env.storage().persistent().remove(&band_key),
+            }
+        }
+        // Late deposits must not shift the band either.
+        let settled_to = band.map(|(_, hi)| hi).unwrap_or(last_claimed_idx);
+        Self::raise_claim_watermark(&env, &token, settled_to);
+        Self::extend_holder_ttl(&env, &token, &holder);
+        Self::extend_instance(&env);
+
+        let history_count_key = ClaimKey::HistoryCount(token.clone(), holder.clone());
+        let seq: u32 = env
+            .storage()
+            .persistent()
+            .get(&history_count_key)
+            .unwrap_or(0);
+        let record = ClaimRecord {
+            periods: claimed_periods.clone(),
+            payouts: period_payouts,
+            amount,
+            timestamp: now,
+            donated,
+            bonus: bonus_paid,
+            share_bps,
+            balance,
+        };
+        env.storage().persistent().set(
+            &ClaimKey::History(token.clone(), holder.clone(), seq),
+            &record,
+        );
+        env.storage()
+            .persistent()
+            .set(&history_count_key, &(seq + 1));
 
+        let periods_claimed = claimed_periods.len();
+        if Self::is_event_versioning_enabled(env.clone()) {
+            env.events().publish(
+                (EVENT_CLAIM_V2, holder.clone(), token.clone()),
+                ClaimedV2 {
+                    amount,
+                    bonus: bonus_paid,
+                    donated,
+                    periods: claimed_periods.clone(),
+                    share_bps,
+                },
+            );
+        }
         env.events().publish(
             (EVENT_CLAIM, holder.clone(), token),
             (total_payout, claimed_periods),
         );
 
-        Ok(total_payout)
+        Ok(ClaimResult {
+            amount,
+            periods_claimed,
+            more_pending: last_claimed_idx < period_count,
+        })
     }
 
-    /// Return unclaimed period IDs for a holder on an offering.
-    pub fn get_pending_periods(env: Env, token: Address, holder: Address) -> Vec<u64> {
-        let count_key = DataKey::PeriodCount(token.clone());
-        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-
-        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder);
-        let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
-
-        let mut periods = Vec::new(&env);
-        for i in start_idx..period_count {
-            let entry_key = DataKey::PeriodEntry(token.clone(), i);
-            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
-            periods.push_back(period_id);
+    /// Bonus earned on `payout` for a period deposited at `deposit_time`.
+    fn prompt_bonus(bonus: &Option<ClaimBonus>, payout: i128, deposit_time: u64, now: u64) -> i128 {
+        match bonus {
+            Some(b) if payout > 0 && now < deposit_time.saturating_add(b.window_secs) => {
+                math::bps_of(payout, b.bonus_bps)
+            }
+            _ => 0,
         }
-        periods
     }
 
-    /// Preview the total claimable amount for a holder without claiming.
-    /// Respects per-offering claim delay (#27): only sums periods past the delay.
-    pub fn get_claimable(env: Env, token: Address, holder: Address) -> i128 {
+    /// Sum what `holder` is owed across periods past the claim delay, counting only the
+    /// unlocked part of each period when `stream_secs` is non-zero.
+    fn pending_payout(env: &Env, token: &Address, holder: &Address, stream_secs: u64) -> i128 {
+        let (env, token, holder) = (env.clone(), token.clone(), holder.clone());
         let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
-        if share_bps == 0 {
+        let balance = Self::live_balance(&env, &token, &holder);
+        if share_bps == 0 && balance.unwrap_or(0) == 0 {
             return 0;
         }
 
@@ -1091,8 +2596,7 @@ impl RevoraRevenueShare {
         let idx_key = DataKey::LastClaimedIdx(token.clone(), holder.clone());
         let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
 
-        let delay_key = DataKey::ClaimDelaySecs(token.clone());
-        let delay_secs: u64 = env.storage().persistent().get(&delay_key).unwrap_or(0);
+        let delays = Self::claim_delay_schedule(&env, &token);
         let now = env.ledger().timestamp();
 
         let mut total: i128 = 0;
@@ -1101,419 +2605,552 @@ impl RevoraRevenueShare {
             let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
             let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
             let deposit_time: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
-            if delay_secs > 0 && now < deposit_time.saturating_add(delay_secs) {
+            if Self::in_claim_delay(&env, &delays, deposit_time) {
+                break;
+            }
+            if Self::is_period_paused(env.clone(), token.clone(), period_id) {
                 break;
             }
-            let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
-            let revenue: i128 = env.storage().persistent().get(&rev_key).unwrap();
-            total += revenue * (share_bps as i128) / 10_000;
+            total += Self::streamed_payout(
+                &env,
+                &token,
+                period_id,
+                &holder,
+                share_bps,
+                balance,
+                now.saturating_sub(deposit_time),
+                stream_secs,
+            )
+            .0;
         }
         total
     }
 
-    // ── Time-delayed claim configuration (#27) ──────────────────
-
-    /// Set per-offering claim delay in seconds. Only issuer may set. 0 = immediate claim.
-    pub fn set_claim_delay(
-        env: Env,
-        issuer: Address,
-        token: Address,
-        delay_secs: u64,
-    ) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
-
-        // Verify offering exists and issuer is current
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
-
-        if current_issuer != issuer {
-            return Err(RevoraError::OfferingNotFound);
-        }
-
-        issuer.require_auth();
-        let key = DataKey::ClaimDelaySecs(token.clone());
-        env.storage().persistent().set(&key, &delay_secs);
-        env.events()
-            .publish((EVENT_CLAIM_DELAY_SET, issuer, token), delay_secs);
-        Ok(())
+    /// Share-based payout still owed to `holder` for one period: the entitlement minus
+    /// anything already pushed by `distribute_batch`. `balance` is the holder's live token
+    /// balance when the offering derives shares from balances.
+    fn share_payout(
+        env: &Env,
+        token: &Address,
+        period_id: u64,
+        holder: &Address,
+        share_bps: u32,
+        balance: Option<i128>,
+    ) -> i128 {
+        let pushed: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PushPaid(token.clone(), period_id, holder.clone()))
+            .unwrap_or(0);
+        (Self::share_entitlement(env, token, period_id, share_bps, balance) - pushed).max(0)
     }
 
-    /// Get per-offering claim delay in seconds. 0 = immediate claim.
-    pub fn get_claim_delay(env: Env, token: Address) -> u64 {
-        let key = DataKey::ClaimDelaySecs(token);
-        env.storage().persistent().get(&key).unwrap_or(0)
+    /// Payout for one period under streaming: the entitlement unlocks linearly over
+    /// `stream_secs` from the deposit, less anything already paid. Returns the payout and
+    /// whether the period is fully unlocked; without streaming it matches `share_payout`.
+    #[allow(clippy::too_many_arguments)]
+    fn streamed_payout(
+        env: &Env,
+        token: &Address,
+        period_id: u64,
+        holder: &Address,
+        share_bps: u32,
+        balance: Option<i128>,
+        elapsed: u64,
+        stream_secs: u64,
+    ) -> (i128, bool) {
+        if elapsed >= stream_secs {
+            let payout = Self::share_payout(env, token, period_id, holder, share_bps, balance);
+            return (payout, true);
+        }
+        let paid = Self::get_pushed_amount(env.clone(), token.clone(), period_id, holder.clone());
+        let entitlement = Self::share_entitlement(env, token, period_id, share_bps, balance);
+        let unlocked = entitlement * elapsed as i128 / stream_secs as i128;
+        ((unlocked - paid).max(0), false)
     }
 
-    /// Return the total number of deposited periods for an offering token.
-    pub fn get_period_count(env: Env, token: Address) -> u32 {
-        let count_key = DataKey::PeriodCount(token);
-        env.storage().persistent().get(&count_key).unwrap_or(0)
+    /// Full share-based entitlement for one period. Swept periods pay nothing; otherwise
+    /// as `period_entitlement`.
+    fn share_entitlement(
+        env: &Env,
+        token: &Address,
+        period_id: u64,
+        share_bps: u32,
+        balance: Option<i128>,
+    ) -> i128 {
+        if env
+            .storage()
+            .persistent()
+            .has(&EscrowKey::Swept(token.clone(), period_id))
+        {
+            return 0;
+        }
+        Self::period_entitlement(env, token, period_id, share_bps, balance)
     }
 
-    // ── On-chain distribution simulation (#29) ────────────────────
-
-    /// Read-only: simulate distribution for sample inputs without mutating state.
-    /// Returns expected payouts per holder and total. Uses offering's rounding mode.
-    /// For integrators to preview outcomes before executing deposit/claim flows.
-    pub fn simulate_distribution(
-        env: Env,
-        issuer: Address,
-        token: Address,
-        amount: i128,
-        holder_shares: Vec<(Address, u32)>,
-    ) -> SimulateDistributionResult {
-        let mode = Self::get_rounding_mode(env.clone(), issuer, token.clone());
-        let mut total: i128 = 0;
-        let mut payouts = Vec::new(&env);
-        for i in 0..holder_shares.len() {
-            let (holder, share_bps) = holder_shares.get(i).unwrap();
-            let payout = if share_bps > 10_000 {
-                0_i128
-            } else {
-                Self::compute_share(env.clone(), amount, share_bps, mode)
-            };
-            total = total.saturating_add(payout);
-            payouts.push_back((holder.clone(), payout));
+    /// Share-based entitlement for one period as deposited, regardless of any later sweep.
+    /// Periods distributed by Merkle root pay nothing here; those holders use
+    /// `claim_with_proof`. Periods deposited in balance mode pay `balance * revenue /
+    /// supply` when a balance is given; earlier periods keep using `share_bps`.
+    fn period_entitlement(
+        env: &Env,
+        token: &Address,
+        period_id: u64,
+        share_bps: u32,
+        balance: Option<i128>,
+    ) -> i128 {
+        if env
+            .storage()
+            .persistent()
+            .has(&MerkleKey::Root(token.clone(), period_id))
+        {
+            return 0;
         }
-        SimulateDistributionResult {
-            total_distributed: total,
-            payouts,
+        if env
+            .storage()
+            .persistent()
+            .has(&SnapshotKey::ForPeriod(token.clone(), period_id))
+        {
+            return 0;
         }
-    }
-
-    // ── Upgradeability guard and freeze (#32) ───────────────────
-
-    /// Set the admin address. May only be called once; caller must authorize as the new admin.
-    pub fn set_admin(env: Env, admin: Address) -> Result<(), RevoraError> {
-        admin.require_auth();
-        let key = DataKey::Admin;
-        if env.storage().persistent().has(&key) {
-            return Err(RevoraError::LimitReached);
+        let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+        let revenue: i128 = env.storage().persistent().get(&rev_key).unwrap();
+        if let Some(balance) = balance {
+            let supply_key = SnapshotKey::PeriodSupply(token.clone(), period_id);
+            if let Some(supply) = env.storage().persistent().get::<_, i128>(&supply_key) {
+                // Only a payout beyond i128 fails, which needs a balance far above the
+                // declared supply; cap it at the period's revenue.
+                return math::pro_rata(revenue, balance, supply).unwrap_or(revenue);
+            }
         }
-        env.storage().persistent().set(&key, &admin);
-        Ok(())
-    }
-
-    /// Get the admin address, if set.
-    pub fn get_admin(env: Env) -> Option<Address> {
-        let key = DataKey::Admin;
-        env.storage().persistent().get(&key)
+        math::bps_of(revenue, share_bps)
     }
 
-    /// Freeze the contract: no further state-changing operations allowed. Only admin may call.
-    /// Emits event. Claim and read-only functions remain allowed.
-    pub fn freeze(env: Env) -> Result<(), RevoraError> {
-        let key = DataKey::Admin;
-        let admin: Address = env
-            .storage()
+    fn period_distributed(env: &Env, token: &Address, period_id: u64) -> i128 {
+        env.storage()
             .persistent()
-            .get(&key)
-            .ok_or(RevoraError::LimitReached)?;
-        admin.require_auth();
-        let frozen_key = DataKey::Frozen;
-        env.storage().persistent().set(&frozen_key, &true);
-        env.events().publish((EVENT_FREEZE, admin), true);
-        Ok(())
+            .get(&EscrowKey::Distributed(token.clone(), period_id))
+            .unwrap_or(0)
     }
 
-    /// Return true if the contract is frozen.
-    pub fn is_frozen(env: Env) -> bool {
+    /// Add `amount` to a period's distributed total.
+    fn record_distributed(env: &Env, token: &Address, period_id: u64, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        let key = EscrowKey::Distributed(token.clone(), period_id);
+        let distributed: i128 = env.storage().persistent().get(&key).unwrap_or(0);
         env.storage()
             .persistent()
-            .get::<DataKey, bool>(&DataKey::Frozen)
-            .unwrap_or(false)
+            .set(&key, &(distributed + amount));
     }
 
-    // ── Secure issuer transfer (two-step flow) ─────────────────
-
-    /// Propose transferring issuer control of an offering to a new address.
-    /// Only the current issuer may call this. Initiates a two-step transfer.
-    pub fn propose_issuer_transfer(
-        env: Env,
-        token: Address,
-        new_issuer: Address,
-    ) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
-
-        // Get current issuer and verify offering exists
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
-
-        // Only current issuer can propose transfer
-        current_issuer.require_auth();
-
-        // Check if transfer already pending
-        let pending_key = DataKey::PendingIssuerTransfer(token.clone());
-        if env.storage().persistent().has(&pending_key) {
-            return Err(RevoraError::IssuerTransferPending);
+    /// Mark a period closed and transfer whatever it has not paid out to `to`.
+    /// Returns the amount transferred.
+    fn close_period(env: &Env, token: &Address, period_id: u64, to: &Address) -> i128 {
+        let escrow = Self::get_period_escrow(env.clone(), token.clone(), period_id);
+        let residual = (escrow.deposited - escrow.distributed).max(0);
+        env.storage()
+            .persistent()
+            .set(&EscrowKey::Swept(token.clone(), period_id), &residual);
+        if residual > 0 {
+            let payment_token: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PaymentToken(token.clone()))
+                .unwrap();
+            token::Client::new(env, &payment_token).transfer(
+                &env.current_contract_address(),
+                to,
+                &residual,
+            );
         }
-
-        // Store pending transfer
-        env.storage().persistent().set(&pending_key, &new_issuer);
-
-        env.events().publish(
-            (EVENT_ISSUER_TRANSFER_PROPOSED, token.clone()),
-            (current_issuer, new_issuer),
-        );
-
-        Ok(())
+        residual
     }
 
-    /// Accept a pending issuer transfer. Only the proposed new issuer may call this.
-    /// Completes the two-step transfer and grants full issuer control to the new address.
-    pub fn accept_issuer_transfer(env: Env, token: Address) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
-
-        // Get pending transfer
-        let pending_key = DataKey::PendingIssuerTransfer(token.clone());
-        let new_issuer: Address = env
+    /// True if no holder is still owed anything for the period: every indexed holder
+    /// with a share is blacklisted, fully pushed, or has claimed past `period_idx`.
+    /// Merkle periods and holder indexes above MAX_SWEEP_HOLDER_SCAN never qualify.
+    fn all_holders_settled(env: &Env, token: &Address, period_id: u64, period_idx: u32) -> bool {
+        if env
             .storage()
             .persistent()
-            .get(&pending_key)
-            .ok_or(RevoraError::NoTransferPending)?;
-
-        // Only the proposed new issuer can accept
-        new_issuer.require_auth();
-
-        // Get current issuer
-        let old_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
-
-        // Update the offering's issuer field in storage
-        // We need to find and update the offering
-        let offering = Self::get_offering(env.clone(), old_issuer.clone(), token.clone())
-            .ok_or(RevoraError::OfferingNotFound)?;
-
-        // Find the index of this offering
-        let count = Self::get_offering_count(env.clone(), old_issuer.clone());
-        let mut found_index: Option<u32> = None;
+            .has(&MerkleKey::Root(token.clone(), period_id))
+        {
+            return false;
+        }
+        let count = Self::get_holder_count(env.clone(), token.clone());
+        if count > MAX_SWEEP_HOLDER_SCAN {
+            return false;
+        }
         for i in 0..count {
-            let item_key = DataKey::OfferItem(old_issuer.clone(), i);
-            let stored_offering: Offering = env.storage().persistent().get(&item_key).unwrap();
-            if stored_offering.token == token {
-                found_index = Some(i);
-                break;
+            let holder: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::HolderAt(token.clone(), i))
+                .unwrap();
+            let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+            if share_bps == 0 || Self::is_blacklisted(env.clone(), token.clone(), holder.clone()) {
+                continue;
+            }
+            let claimed_idx: u32 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::LastClaimedIdx(token.clone(), holder.clone()))
+                .unwrap_or(0);
+            if claimed_idx > period_idx {
+                continue;
+            }
+            if Self::share_payout(env, token, period_id, &holder, share_bps, None) > 0 {
+                return false;
             }
         }
+        true
+    }
 
-        let index = found_index.ok_or(RevoraError::OfferingNotFound)?;
+    /// Live offering-token balance of `holder` when the offering is in balance mode.
+    fn live_balance(env: &Env, token: &Address, holder: &Address) -> Option<i128> {
+        Self::get_balance_mode(env.clone(), token.clone())
+            .map(|_| token::Client::new(env, token).balance(holder))
+    }
 
-        // Update the offering with new issuer
-        let updated_offering = Offering {
-            issuer: new_issuer.clone(),
-            token: token.clone(),
-            revenue_share_bps: offering.revenue_share_bps,
-            payout_asset: offering.payout_asset,
-        };
+    /// Walk `proof` from `leaf` using sorted-pair hashing and compare with `root`.
+    /// Proofs longer than MAX_MERKLE_PROOF_LEN are rejected.
+    fn verify_merkle_proof(
+        env: &Env,
+        root: &BytesN<32>,
+        leaf: BytesN<32>,
+        proof: &Vec<BytesN<32>>,
+    ) -> bool {
+        if proof.len() > MAX_MERKLE_PROOF_LEN {
+            return false;
+        }
+        let mut node = leaf;
+        for sibling in proof.iter() {
+            let (a, b) = if node.to_array() <= sibling.to_array() {
+                (node, sibling)
+            } else {
+                (sibling, node)
+            };
+            let mut buf = Bytes::from_array(env, &a.to_array());
+            buf.append(&Bytes::from_array(env, &b.to_array()));
+            node = env.crypto().sha256(&buf).to_bytes();
+        }
+        node == *root
+    }
 
-        // Remove from old issuer's storage
-        let old_item_key = DataKey::OfferItem(old_issuer.clone(), index);
-        env.storage().persistent().remove(&old_item_key);
+    /// Claim delay steps for `token`. Offerings whose delay predates the schedule read as
+    /// one step covering every deposit.
+    fn claim_delay_schedule(env: &Env, token: &Address) -> Vec<(u64, u64)> {
+        let store = env.storage().persistent();
+        if let Some(schedule) = store.get(&ClaimKey::DelaySchedule(token.clone())) {
+            return schedule;
+        }
+        let secs: u64 = store
+            .get(&DataKey::ClaimDelaySecs(token.clone()))
+            .unwrap_or(0);
+        if secs == 0 {
+            Vec::new(env)
+        } else {
+            Vec::from_array(env, [(0, secs)])
+        }
+    }
 
-        // If this wasn't the last offering, move the last offering to fill the gap
-        let old_count = Self::get_offering_count(env.clone(), old_issuer.clone());
-        if index < old_count - 1 {
-            // Move the last offering to the removed index
-            let last_key = DataKey::OfferItem(old_issuer.clone(), old_count - 1);
-            let last_offering: Offering = env.storage().persistent().get(&last_key).unwrap();
-            env.storage()
-                .persistent()
-                .set(&old_item_key, &last_offering);
-            env.storage().persistent().remove(&last_key);
+    /// Delay for a period deposited at `deposit_time`.
+    fn delay_at(schedule: &Vec<(u64, u64)>, deposit_time: u64) -> u64 {
+        let mut delay = 0;
+        for (from, secs) in schedule.iter() {
+            if from > deposit_time {
+                break;
+            }
+            delay = secs;
         }
+        delay
+    }
 
-        // Decrement old issuer's count
-        let old_count_key = DataKey::OfferCount(old_issuer.clone());
-        env.storage()
-            .persistent()
-            .set(&old_count_key, &(old_count - 1));
+    /// Whether a period deposited at `deposit_time` is still within its claim delay.
+    fn in_claim_delay(env: &Env, schedule: &Vec<(u64, u64)>, deposit_time: u64) -> bool {
+        let delay = Self::delay_at(schedule, deposit_time);
+        delay > 0 && env.ledger().timestamp() < deposit_time.saturating_add(delay)
+    }
 
-        // Add to new issuer's storage
-        let new_count = Self::get_offering_count(env.clone(), new_issuer.clone());
-        let new_item_key = DataKey::OfferItem(new_issuer.clone(), new_count);
-        env.storage()
-            .persistent()
-            .set(&new_item_key, &updated_offering);
+    /// Export page size: `limit`, or the network page limit when 0 or above it.
+    fn export_limit(env: &Env, limit: u32) -> u32 {
+        let page_limit = Self::get_network_limits(env.clone()).page_limit;
+        if limit == 0 || limit > page_limit {
+            page_limit
+        } else {
+            limit
+        }
+    }
 
-        // Increment new issuer's count
-        let new_count_key = DataKey::OfferCount(new_issuer.clone());
-        env.storage()
+    /// Returns `PendingClaimsOutstanding` if `holder` has deposited periods left to claim.
+    /// Shares are paid out at claim time, so moving bps with pending periods would
+    /// re-price entitlements that already accrued.
+    fn require_claims_settled(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+    ) -> Result<(), RevoraError> {
+        let period_count: u32 = env
+            .storage()
             .persistent()
-            .set(&new_count_key, &(new_count + 1));
-
-        // Update reverse lookup
-        let issuer_lookup_key = DataKey::OfferingIssuer(token.clone());
-        env.storage()
+            .get(&DataKey::PeriodCount(token.clone()))
+            .unwrap_or(0);
+        let share = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+        let claimed: u32 = env
+            .storage()
             .persistent()
-            .set(&issuer_lookup_key, &new_issuer);
-
-        // Clear pending transfer
-        env.storage().persistent().remove(&pending_key);
-
-        env.events().publish(
-            (EVENT_ISSUER_TRANSFER_ACCEPTED, token),
-            (old_issuer, new_issuer),
-        );
-
+            .get(&DataKey::LastClaimedIdx(token.clone(), holder.clone()))
+            .unwrap_or(0);
+        if share > 0 && claimed < period_count {
+            return Err(RevoraError::PendingClaimsOutstanding);
+        }
         Ok(())
     }
 
-    /// Cancel a pending issuer transfer. Only the current issuer may call this.
-    pub fn cancel_issuer_transfer(env: Env, token: Address) -> Result<(), RevoraError> {
-        Self::require_not_frozen(&env)?;
-
-        // Get current issuer
-        let current_issuer =
-            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
-
-        // Only current issuer can cancel
-        current_issuer.require_auth();
+    /// Eligibility checks for anyone receiving holder share: blacklists, KYC, offering
+    /// restrictions, whitelist and terms acknowledgement.
+    fn require_share_eligible(
+        env: &Env,
+        token: &Address,
+        holder: &Address,
+    ) -> Result<(), RevoraError> {
+        if Self::is_globally_blacklisted(env.clone(), holder.clone())
+            || Self::is_blacklisted(env.clone(), token.clone(), holder.clone())
+        {
+            return Err(RevoraError::HolderBlacklisted);
+        }
+        Self::require_kyc(env, token, holder)?;
+        Self::require_restrictions_met(env, token, holder)?;
+        Self::require_whitelisted(env, token, holder)?;
+        Self::require_terms_acknowledged(env, token, holder)
+    }
 
-        // Check if transfer is pending
-        let pending_key = DataKey::PendingIssuerTransfer(token.clone());
-        let proposed_new_issuer: Address = env
+    /// Move `share_bps` from `from` to `to`. New recipients start claiming at the next
+    /// deposited period so they never receive revenue from before the transfer.
+    fn move_holder_share(
+        env: &Env,
+        token: &Address,
+        from: &Address,
+        to: &Address,
+        share_bps: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_share_eligible(env, token, to)?;
+        Self::require_claims_settled(env, token, from)?;
+        Self::require_claims_settled(env, token, to)?;
+        let from_share = Self::get_holder_share(env.clone(), token.clone(), from.clone());
+        let to_share = Self::get_holder_share(env.clone(), token.clone(), to.clone());
+        if share_bps == 0 || share_bps > from_share || to_share + share_bps > 10_000 {
+            return Err(RevoraError::InvalidShareBps);
+        }
+        let period_count: u32 = env
             .storage()
             .persistent()
-            .get(&pending_key)
-            .ok_or(RevoraError::NoTransferPending)?;
-
-        // Clear pending transfer
-        env.storage().persistent().remove(&pending_key);
-
-        env.events().publish(
-            (EVENT_ISSUER_TRANSFER_CANCELLED, token),
-            (current_issuer, proposed_new_issuer),
+            .get(&DataKey::PeriodCount(token.clone()))
+            .unwrap_or(0);
+        env.storage().persistent().set(
+            &DataKey::LastClaimedIdx(token.clone(), to.clone()),
+            &period_count,
         );
+        Self::raise_claim_watermark(env, token, period_count);
+        Self::write_holder_share(env, token, from, from_share - share_bps)?;
+        Self::write_holder_share(env, token, to, to_share + share_bps)
+    }
 
+    /// Commit `share_bps` of `seller`'s share to a listing or pending transfer. Fails with
+    /// `InvalidShareBps` if it is zero or more than the share not already committed, so the
+    /// same bps cannot be sold twice.
+    fn commit_share(
+        env: &Env,
+        token: &Address,
+        seller: &Address,
+        share_bps: u32,
+    ) -> Result<(), RevoraError> {
+        let key = EscrowKey::CommittedBps(token.clone(), seller.clone());
+        let committed: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let share = Self::get_holder_share(env.clone(), token.clone(), seller.clone());
+        if share_bps == 0 || share_bps > share.saturating_sub(committed) {
+            return Err(RevoraError::InvalidShareBps);
+        }
+        env.storage()
+            .persistent()
+            .set(&key, &(committed + share_bps));
         Ok(())
     }
 
-    /// Get the pending issuer transfer for an offering, if any.
-    pub fn get_pending_issuer_transfer(env: Env, token: Address) -> Option<Address> {
-        let pending_key = DataKey::PendingIssuerTransfer(token);
-        env.storage().persistent().get(&pending_key)
+    /// Release bps committed by `commit_share` once its listing or transfer is settled.
+    fn release_share(env: &Env, token: &Address, seller: &Address, share_bps: u32) {
+        let key = EscrowKey::CommittedBps(token.clone(), seller.clone());
+        let committed: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let left = committed.saturating_sub(share_bps);
+        if left == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &left);
+        }
     }
 
-    // ── Revenue distribution calculation ───────────────────────────
-
-    /// Calculate the distribution amount for a token holder.
-    ///
-    /// This function computes the payout amount for a single holder using
-    /// fixed-point arithmetic with basis points (BPS) precision.
-    ///
-    /// Formula:
-    ///   distributable_revenue = total_revenue * revenue_share_bps / BPS_DENOMINATOR
-    ///   holder_payout = holder_balance * distributable_revenue / total_supply
-    ///
-    /// Rounding: Uses integer division which rounds down (floor).
-    /// This is conservative and ensures the contract never over-distributes.
-    #[allow(clippy::too_many_arguments)]
-    pub fn calculate_distribution(
-        env: Env,
-        caller: Address,
-        issuer: Address,
-        token: Address,
-        total_revenue: i128,
-        total_supply: i128,
-        holder_balance: i128,
-        holder: Address,
-    ) -> i128 {
-        caller.require_auth();
-
-        if total_supply == 0 {
-            panic!("total_supply cannot be zero");
+    /// Validate and record a pending share transfer, escrowing `price` from `buyer`.
+    fn open_share_transfer(
+        env: &Env,
+        seller: &Address,
+        buyer: &Address,
+        token: &Address,
+        share_bps: u32,
+        price: i128,
+        config: &ShareTransferConfig,
+    ) -> Result<u32, RevoraError> {
+        if price < 0 {
+            return Err(RevoraError::InvalidAmount);
         }
-
-        let offering = Self::get_offering(env.clone(), issuer.clone(), token.clone())
-            .expect("offering not found");
-
-        if Self::is_blacklisted(env.clone(), token.clone(), holder.clone()) {
-            panic!("holder is blacklisted and cannot receive distribution");
+        Self::require_share_eligible(env, token, buyer)?;
+        if seller == buyer {
+            return Err(RevoraError::InvalidShareBps);
         }
+        Self::commit_share(env, token, seller, share_bps)?;
 
-        if total_revenue == 0 || holder_balance == 0 {
-            let payout = 0i128;
-            env.events().publish(
-                (EVENT_DIST_CALC, token.clone(), holder.clone()),
-                (
-                    total_revenue,
-                    total_supply,
-                    holder_balance,
-                    offering.revenue_share_bps,
-                    payout,
-                ),
+        let issuer = Self::get_current_issuer(env, token).ok_or(RevoraError::OfferingNotFound)?;
+        let offering = Self::get_offering(env.clone(), issuer, token.clone())
+            .ok_or(RevoraError::OfferingNotFound)?;
+        if price > 0 {
+            token::Client::new(env, &offering.payout_asset).transfer(
+                buyer,
+                &env.current_contract_address(),
+                &price,
             );
-            return payout;
         }
 
-        let distributable_revenue = (total_revenue * offering.revenue_share_bps as i128)
-            .checked_div(BPS_DENOMINATOR)
-            .expect("division overflow");
-
-        let payout = (holder_balance * distributable_revenue)
-            .checked_div(total_supply)
-            .expect("division overflow");
+        let count_key = DataKey::ShareTransferCount(token.clone());
+        let id: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let transfer = ShareTransfer {
+            seller: seller.clone(),
+            buyer: buyer.clone(),
+            share_bps,
+            price,
+            rofr_deadline: env
+                .ledger()
+                .timestamp()
+                .saturating_add(config.rofr_window_secs),
+            matched: false,
+            completed: false,
+            cancelled: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::ShareTransfer(token.clone(), id), &transfer);
+        env.storage().persistent().set(&count_key, &(id + 1));
 
         env.events().publish(
-            (EVENT_DIST_CALC, token, holder),
+            (EVENT_SHARE_XFER_PROPOSED, token.clone(), id),
             (
-                total_revenue,
-                total_supply,
-                holder_balance,
-                offering.revenue_share_bps,
-                payout,
+                seller.clone(),
+                buyer.clone(),
+                share_bps,
+                price,
+                transfer.rofr_deadline,
             ),
         );
-
-        payout
+        Ok(id)
     }
 
-    /// Calculate the total distributable revenue for an offering.
-    ///
-    /// This is a helper function for off-chain verification.
-    pub fn calculate_total_distributable(
-        env: Env,
-        issuer: Address,
-        token: Address,
-        total_revenue: i128,
-    ) -> i128 {
-        let offering =
-            Self::get_offering(env, issuer, token).expect("offering not found for token");
-
-        if total_revenue == 0 {
-            return 0;
+    // ── Reporter reputation / strikes ──────────────────────────
+
+    /// Record a strike against `issuer` and suspend reporting once the configured
+    /// threshold is reached. Returns the active strike count.
+    fn add_strike(env: &Env, issuer: &Address, reason: StrikeReason) -> u32 {
+        let key = DataKey::IssuerReputation(issuer.clone());
+        let mut rep = Self::get_issuer_reputation(env.clone(), issuer.clone());
+        rep.strikes = rep.strikes.saturating_add(1);
+        match reason {
+            StrikeReason::RejectedOverride => {
+                rep.rejected_overrides = rep.rejected_overrides.saturating_add(1)
+            }
+            StrikeReason::UpheldDispute => {
+                rep.upheld_disputes = rep.upheld_disputes.saturating_add(1)
+            }
+            StrikeReason::LateReport => rep.late_reports = rep.late_reports.saturating_add(1),
         }
 
-        (total_revenue * offering.revenue_share_bps as i128)
-            .checked_div(BPS_DENOMINATOR)
-            .expect("division overflow")
+        let threshold = Self::get_config(env.clone()).strike_threshold;
+        if threshold > 0 && !rep.suspended && rep.strikes >= threshold {
+            rep.suspended = true;
+            env.events()
+                .publish((EVENT_ISSUER_SUSPENDED, issuer.clone()), rep.strikes);
+        }
+        env.storage().persistent().set(&key, &rep);
+        rep.strikes
     }
 
-    // ── Testnet mode configuration (#24) ───────────────────────
+    fn clear_pending_transfer(env: &Env, token: &Address, new_issuer: &Address) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingIssuerTransfer(token.clone()));
+        env.storage()
+            .persistent()
+            .remove(&OfferingKey::TransferProposedAt(token.clone()));
+        Self::remove_from_transfer_inbox(env, new_issuer, token);
+        Self::unindex_pending_transfer(env, token);
+    }
 
-    /// Enable or disable testnet mode. Only admin may call.
-    /// When enabled, certain validations are relaxed for testnet deployments.
-    /// Emits event with new mode state.
-    pub fn set_testnet_mode(env: Env, enabled: bool) -> Result<(), RevoraError> {
-        let key = DataKey::Admin;
-        let admin: Address = env
-            .storage()
+    fn index_pending_transfer(env: &Env, token: &Address) {
+        let count = Self::get_pending_transfer_count(env.clone());
+        env.storage()
             .persistent()
-            .get(&key)
-            .ok_or(RevoraError::LimitReached)?;
-        admin.require_auth();
-        let mode_key = DataKey::TestnetMode;
-        env.storage().persistent().set(&mode_key, &enabled);
-        env.events().publish((EVENT_TESTNET_MODE, admin), enabled);
-        Ok(())
+            .set(&OfferingKey::PendingTransferAt(count), token);
+        env.storage()
+            .persistent()
+            .set(&OfferingKey::PendingTransferPos(token.clone()), &count);
+        env.storage()
+            .persistent()
+            .set(&OfferingKey::PendingTransferCount, &(count + 1));
     }
 
-    /// Return true if testnet mode is enabled.
-    pub fn is_testnet_mode(env: Env) -> bool {
+    /// Swap-remove `token` from the global pending-transfer index.
+    fn unindex_pending_transfer(env: &Env, token: &Address) {
+        let pos_key = OfferingKey::PendingTransferPos(token.clone());
+        let pos: u32 = match env.storage().persistent().get(&pos_key) {
+            Some(p) => p,
+            None => return,
+        };
+        let last = Self::get_pending_transfer_count(env.clone()) - 1;
+        if pos < last {
+            let moved: Address = env
+                .storage()
+                .persistent()
+                .get(&OfferingKey::PendingTransferAt(last))
+                .unwrap();
+            env.storage()
+                .persistent()
+                .set(&OfferingKey::PendingTransferAt(pos), &moved);
+            env.storage()
+                .persistent()
+                .set(&OfferingKey::PendingTransferPos(moved), &pos);
+        }
         env.storage()
             .persistent()
-            .get::<DataKey, bool>(&DataKey::TestnetMode)
-            .unwrap_or(false)
+            .remove(&OfferingKey::PendingTransferAt(last));
+        env.storage().persistent().remove(&pos_key);
+        env.storage()
+            .persistent()
+            .set(&OfferingKey::PendingTransferCount, &last);
+    }
+
+    fn remove_from_transfer_inbox(env: &Env, address: &Address, token: &Address) {
+        let inbox_key = OfferingKey::TransferInbox(address.clone());
+        let mut inbox: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&inbox_key)
+            .unwrap_or_else(|| Vec::new(env));
+        if let Some(i) = inbox.first_index_of(token) {
+            inbox.remove(i);
+        }
+        if inbox.is_empty() {
+            env.storage().persistent().remove(&inbox_key);
+        } else {
+            env.storage().persistent().set(&inbox_key, &inbox);
+        }
     }
 }
 
@@ -0,0 +1,115 @@
+//! Share and distribution math.
+//!
+//! Pure integer arithmetic with no `Env` or storage access, so back-office tooling and
+//! off-chain distribution engines can depend on this crate and get byte-identical
+//! results to the contract instead of reimplementing the formulas.
+
+use soroban_sdk::contracttype;
+
+/// Basis-point denominator: 10_000 bps = 100%.
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Rounding mode for distribution share calculations (#44).
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Truncate toward zero: share = (amount * bps) / 10000
+    Truncation = 0,
+    /// Round half up: share = (amount * bps * 2 + 10000) / 20000
+    RoundHalfUp = 1,
+}
+
+/// `amount * bps / 10_000`, truncating toward zero. Claims, platform fees, referral
+/// cuts, donations and bonuses all use this.
+pub fn bps_of(amount: i128, bps: u32) -> i128 {
+    amount * bps as i128 / BPS_DENOMINATOR
+}
+
+/// Share of `amount` at `revenue_share_bps` using the given rounding mode. The result
+/// lies between 0 and `amount` (inclusive); 0 above 10_000 bps or on overflow.
+pub fn compute_share(amount: i128, revenue_share_bps: u32, mode: RoundingMode) -> i128 {
+    if revenue_share_bps > 10_000 {
+        return 0;
+    }
+    let bps = revenue_share_bps as i128;
+    let raw = amount.checked_mul(bps).unwrap_or(0);
+    let share = match mode {
+        RoundingMode::Truncation => raw.checked_div(10_000).unwrap_or(0),
+        RoundingMode::RoundHalfUp => {
+            let half = 5_000_i128;
+            let adjusted = if raw >= 0 {
+                raw.saturating_add(half)
+            } else {
+                raw.saturating_sub(half)
+            };
+            adjusted.checked_div(10_000).unwrap_or(0)
+        }
+    };
+    // Clamp to [min(0, amount), max(0, amount)] to avoid overflow semantics affecting bounds
+    let lo = core::cmp::min(0, amount);
+    let hi = core::cmp::max(0, amount);
+    core::cmp::min(core::cmp::max(share, lo), hi)
+}
+
+/// `amount * part / whole`, truncating toward zero: a holder's pro-rata slice of a
+/// period by balance. The product is formed in 256 bits, so large revenue and balance
+/// figures do not overflow on the way. `None` if `whole` is 0 or the result itself does
+/// not fit in an `i128`.
+pub fn pro_rata(amount: i128, part: i128, whole: i128) -> Option<i128> {
+    if let Some(product) = amount.checked_mul(part) {
+        return product.checked_div(whole);
+    }
+    if whole == 0 {
+        return None;
+    }
+    let negative = (amount < 0) ^ (part < 0) ^ (whole < 0);
+    let (hi, lo) = mul_wide(amount.unsigned_abs(), part.unsigned_abs());
+    let quotient = i128::try_from(div_wide(hi, lo, whole.unsigned_abs())?).ok()?;
+    Some(if negative { -quotient } else { quotient })
+}
+
+/// Full 256-bit product of `a * b` as `(high, low)` halves.
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a1, a0) = (a >> 64, a & MASK);
+    let (b1, b0) = (b >> 64, b & MASK);
+    let (p00, p01, p10, p11) = (a0 * b0, a0 * b1, a1 * b0, a1 * b1);
+    let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+    let lo = (p00 & MASK) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+/// `(hi, lo) / d` by binary long division; `None` when the quotient needs more than
+/// 128 bits.
+fn div_wide(hi: u128, lo: u128, d: u128) -> Option<u128> {
+    if hi >= d {
+        return None;
+    }
+    let mut rem = hi;
+    let mut quotient = 0u128;
+    for bit in (0..128).rev() {
+        let carry = rem >> 127;
+        rem = (rem << 1) | ((lo >> bit) & 1);
+        quotient <<= 1;
+        if carry == 1 || rem >= d {
+            rem = rem.wrapping_sub(d);
+            quotient |= 1;
+        }
+    }
+    Some(quotient)
+}
+
+/// `calculate_distribution` payout: the offering's `revenue_share_bps` of
+/// `total_revenue`, split pro rata by `holder_balance / total_supply`. Rounds down, so
+/// payouts never exceed the distributable revenue. `None` on zero supply or when
+/// `total_revenue * revenue_share_bps` or the payout itself does not fit in an `i128`.
+pub fn holder_payout(
+    total_revenue: i128,
+    revenue_share_bps: u32,
+    holder_balance: i128,
+    total_supply: i128,
+) -> Option<i128> {
+    let distributable = total_revenue.checked_mul(revenue_share_bps as i128)? / BPS_DENOMINATOR;
+    pro_rata(distributable, holder_balance, total_supply)
+}
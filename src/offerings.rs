@@ -0,0 +1,1295 @@
+//! Offering lifecycle entrypoints: registration, period schedules, metadata, holder share
+//! assignment, issuer transfers, capital raises and secondary share transfers.
+
+use super::*;
+
+#[contractimpl]
+impl RevoraRevenueShare {
+    /// Halt a single offering without pausing the platform. Callable by the offering's
+    /// issuer or the admin. Deposits, reports, share changes, claims, raises and share
+    /// trades for the offering fail with `OfferingPaused` until `unpause_offering`.
+    pub fn pause_offering(env: Env, caller: Address, token: Address) -> Result<(), RevoraError> {
+        Self::require_offering_pauser(&env, &caller, &token)?;
+        env.storage()
+            .persistent()
+            .set(&OfferingKey::Paused(token.clone()), &true);
+        env.events().publish((EVENT_OFFERING_PAUSED, token), caller);
+        Ok(())
+    }
+
+    /// Resume an offering paused with `pause_offering`. Issuer or admin.
+    pub fn unpause_offering(env: Env, caller: Address, token: Address) -> Result<(), RevoraError> {
+        Self::require_offering_pauser(&env, &caller, &token)?;
+        env.storage()
+            .persistent()
+            .remove(&OfferingKey::Paused(token.clone()));
+        env.events()
+            .publish((EVENT_OFFERING_UNPAUSED, token), caller);
+        Ok(())
+    }
+
+    /// Register a new revenue-share offering.
+    /// Returns `Err(RevoraError::InvalidRevenueShareBps)` if revenue_share_bps > 10000, and
+    /// `LimitReached` at the per-issuer or platform-wide offering cap (see `ContractConfig`).
+    /// In testnet mode, bps validation is skipped to allow flexible testing.
+    pub fn register_offering(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        revenue_share_bps: u32,
+        payout_asset: Address,
+    ) -> Result<RegisteredOffering, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_not_paused(&env)?;
+        issuer.require_auth();
+
+        // Skip bps validation in testnet mode
+        let testnet_mode = Self::is_testnet_mode(env.clone());
+        if !testnet_mode && revenue_share_bps > 10_000 {
+            return Err(RevoraError::InvalidRevenueShareBps);
+        }
+        if env.storage().persistent().has(&OfferingKey::VerifyToken) {
+            Self::verify_offering_token(&env, &token)?;
+        }
+
+        let count_key = DataKey::OfferCount(issuer.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let store = env.storage().persistent();
+        if store
+            .get::<_, u32>(&OfferingKey::MaxPerIssuer)
+            .is_some_and(|max| count >= max)
+        {
+            return Err(RevoraError::LimitReached);
+        }
+        if !store.has(&OfferingKey::Id(token.clone()))
+            && store
+                .get::<_, u64>(&OfferingKey::MaxTotal)
+                .is_some_and(|max| Self::get_total_offering_count(env.clone()) >= max)
+        {
+            return Err(RevoraError::LimitReached);
+        }
+
+        let offering = Offering {
+            issuer: issuer.clone(),
+            token: token.clone(),
+            revenue_share_bps,
+            payout_asset: payout_asset.clone(),
+        };
+
+        let item_key = DataKey::OfferItem(issuer.clone(), count);
+        env.storage().persistent().set(&item_key, &offering);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        // Direct token -> position lookup; a re-registered token keeps its first entry.
+        let by_token_key = OfferingKey::ByToken(token.clone());
+        if !env.storage().persistent().has(&by_token_key) {
+            env.storage().persistent().set(&by_token_key, &count);
+        }
+
+        // Maintain reverse lookup: token -> issuer
+        let issuer_lookup_key = DataKey::OfferingIssuer(token.clone());
+        env.storage().persistent().set(&issuer_lookup_key, &issuer);
+        Self::update_metrics(&env, |m| m.offerings = m.offerings.saturating_add(1));
+
+        let id_key = OfferingKey::Id(token.clone());
+        let id = match env.storage().persistent().get::<_, u64>(&id_key) {
+            Some(id) => id,
+            None => {
+                let id = env
+                    .storage()
+                    .persistent()
+                    .get::<_, u64>(&OfferingKey::LastId)
+                    .unwrap_or(0)
+                    + 1;
+                env.storage().persistent().set(&OfferingKey::LastId, &id);
+                env.storage().persistent().set(&id_key, &id);
+                env.storage()
+                    .persistent()
+                    .set(&OfferingKey::ById(id), &token);
+                id
+            }
+        };
+
+        env.events().publish(
+            (symbol_short!("offer_reg"), issuer.clone()),
+            (token.clone(), revenue_share_bps, payout_asset.clone()),
+        );
+        // Optionally emit a versioned v1 event with explicit version field
+        if Self::is_event_versioning_enabled(env.clone()) {
+            env.events().publish(
+                (EVENT_OFFER_REG_V1, issuer.clone()),
+                (
+                    EVENT_SCHEMA_VERSION,
+                    token.clone(),
+                    revenue_share_bps,
+                    payout_asset.clone(),
+                ),
+            );
+        }
+        // One-time subscription hint listing every topic this offering may emit.
+        if Self::get_config(env.clone()).topics_hint {
+            env.events().publish(
+                (EVENT_TOPICS_HINT, issuer, token),
+                Self::offering_event_topics(&env),
+            );
+        }
+        Ok(RegisteredOffering {
+            id,
+            index: count,
+            offering,
+        })
+    }
+
+    /// Put `token` on a recurring period calendar (issuer only): period 1 starts at `start`
+    /// and each period lasts `interval_secs`. From then on `deposit_revenue` and
+    /// `report_revenue` accept only periods that have started (`PeriodOffSchedule`).
+    /// `interval_secs` 0 removes the schedule.
+    pub fn set_period_schedule(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        start: u64,
+        interval_secs: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        let key = OfferingKey::PeriodSchedule(token.clone());
+        if interval_secs == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(
+                &key,
+                &PeriodSchedule {
+                    start,
+                    interval_secs,
+                },
+            );
+        }
+        env.events()
+            .publish((EVENT_SCHEDULE_SET, issuer, token), (start, interval_secs));
+        Ok(())
+    }
+
+    /// Check `token`'s deposits against its schedule; callable by anyone. With at least
+    /// `ContractConfig.delinquency_periods` missed periods the offering is flagged
+    /// (`delinq` event, `get_delinquency`); once caught up, the flag is cleared
+    /// (`delinq_ok`). Returns whether the offering is delinquent. `InvalidState` if the
+    /// offering has no schedule.
+    pub fn flag_delinquent(env: Env, token: Address) -> Result<bool, RevoraError> {
+        let issuer = Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if Self::get_period_schedule(env.clone(), token.clone()).is_none() {
+            return Err(RevoraError::InvalidState);
+        }
+        let missed = Self::get_missed_periods(env.clone(), token.clone());
+        let key = OfferingKey::Delinquent(token.clone());
+        if missed.len() >= Self::get_config(env.clone()).delinquency_periods {
+            let status = Delinquency {
+                missed: missed.len(),
+                flagged_at: env.ledger().timestamp(),
+            };
+            env.storage().persistent().set(&key, &status);
+            env.events()
+                .publish((EVENT_DELINQUENT, issuer, token), missed);
+            return Ok(true);
+        }
+        if env.storage().persistent().has(&key) {
+            env.storage().persistent().remove(&key);
+            env.events()
+                .publish((EVENT_DELINQUENCY_CLEARED, issuer), token);
+        }
+        Ok(false)
+    }
+
+    /// Close an offering. Deposits, revenue reports, holder share changes and new raises
+    /// are rejected with `OfferingClosed` afterwards; holders can still claim everything
+    /// already deposited. Closing is permanent.
+    pub fn close_offering(env: Env, issuer: Address, token: Address) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        Self::require_offering_open(&env, &token)?;
+
+        env.storage()
+            .persistent()
+            .set(&OfferingKey::Closed(token.clone()), &true);
+        env.events()
+            .publish((EVENT_OFFERING_CLOSED, issuer, token), ());
+        Ok(())
+    }
+
+    /// Tag `token` with the currency its revenue is reported in, e.g. `USD` (issuer only).
+    /// Informational: amounts are not converted, but v2 report and deposit events carry the
+    /// tag so consumers can aggregate across offerings whose payout assets differ. `None`
+    /// removes it.
+    pub fn set_accounting_currency(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        currency: Option<Symbol>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        let key = OfferingKey::Currency(token.clone());
+        match &currency {
+            Some(symbol) => env.storage().persistent().set(&key, symbol),
+            None => env.storage().persistent().remove(&key),
+        }
+        env.events()
+            .publish((EVENT_CURRENCY_SET, issuer, token), currency);
+        Ok(())
+    }
+
+    /// Set or replace an offering's metadata. Fails with `MetadataTooLarge` if the text or
+    /// bytes exceed `ContractConfig.max_metadata_len`; lowering that limit does not affect
+    /// metadata already stored. Every call also appends a version to the metadata history
+    /// (`get_metadata_at_version`), so replaced terms stay visible.
+    pub fn set_offering_metadata(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        metadata: OfferingMetadata,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        Self::require_metadata_fits(&env, &metadata)?;
+        let store = env.storage().persistent();
+        store.set(&MetadataKey::Offering(token.clone()), &metadata);
+        let count_key = MetadataKey::VersionCount(token.clone());
+        let version: u32 = store.get(&count_key).unwrap_or(0);
+        store.set(
+            &MetadataKey::Version(token.clone(), version),
+            &MetadataRevision {
+                metadata: metadata.clone(),
+                set_at: env.ledger().timestamp(),
+            },
+        );
+        store.set(&count_key, &(version + 1));
+        env.events()
+            .publish((EVENT_METADATA_SET, issuer, token), metadata);
+        Ok(())
+    }
+
+    /// Set or replace the offering's structured metadata. The string fields together must
+    /// fit `ContractConfig.max_metadata_len` bytes (`MetadataTooLarge`). Independent of the
+    /// legacy `set_offering_metadata`, which keeps working.
+    pub fn set_offering_metadata_v2(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        metadata: OfferingMetadataV2,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let len = [
+            &metadata.name,
+            &metadata.symbol,
+            &metadata.description_uri,
+            &metadata.logo_uri,
+            &metadata.website,
+        ]
+        .iter()
+        .fold(0u32, |len, field| len.saturating_add(field.len()));
+        if len > Self::get_config(env.clone()).max_metadata_len {
+            return Err(RevoraError::MetadataTooLarge);
+        }
+        env.storage()
+            .persistent()
+            .set(&MetadataKey::V2(token.clone()), &metadata);
+        env.events()
+            .publish((EVENT_METADATA_V2_SET, issuer, token), metadata);
+        Ok(())
+    }
+
+    /// Set (`Some`) or remove (`None`) the offering's metadata for one locale, e.g. `en` or
+    /// `pt_BR`. Same length limit as `set_offering_metadata`; at most MAX_METADATA_LOCALES
+    /// locales per offering (`LimitReached`).
+    pub fn set_localized_metadata(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        locale: Symbol,
+        metadata: Option<OfferingMetadata>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let key = MetadataKey::Localized(token.clone(), locale.clone());
+        let locales_key = MetadataKey::Locales(token.clone());
+        let mut locales = Self::get_metadata_locales(env.clone(), token.clone());
+        match &metadata {
+            Some(m) => {
+                Self::require_metadata_fits(&env, m)?;
+                if !locales.contains(&locale) {
+                    if locales.len() >= MAX_METADATA_LOCALES {
+                        return Err(RevoraError::LimitReached);
+                    }
+                    locales.push_back(locale.clone());
+                    env.storage().persistent().set(&locales_key, &locales);
+                }
+                env.storage().persistent().set(&key, m);
+            }
+            None => {
+                if let Some(i) = locales.first_index_of(&locale) {
+                    locales.remove(i);
+                    if locales.is_empty() {
+                        env.storage().persistent().remove(&locales_key);
+                    } else {
+                        env.storage().persistent().set(&locales_key, &locales);
+                    }
+                }
+                env.storage().persistent().remove(&key);
+            }
+        }
+        env.events().publish(
+            (EVENT_METADATA_LOCALE_SET, issuer, token),
+            (locale, metadata),
+        );
+        Ok(())
+    }
+
+    /// Set a holder's revenue share (in basis points) for an offering.
+    ///
+    /// Only the offering issuer may call this. `share_bps` must be <= 10000.
+    /// Issuer authorization is bound to `(token, holder, share_bps)`.
+    pub fn set_holder_share(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        holder: Address,
+        share_bps: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        // Verify offering exists and issuer is current
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+
+        issuer.require_auth_for_args((token.clone(), holder.clone(), share_bps).into_val(&env));
+        Self::require_offering_open(&env, &token)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        if share_bps > 10_000 {
+            return Err(RevoraError::InvalidShareBps);
+        }
+        if share_bps > 0 {
+            Self::require_share_eligible(&env, &token, &holder)?;
+        }
+
+        Self::write_holder_share(&env, &token, &holder, share_bps)?;
+
+        env.events()
+            .publish((EVENT_SHARE_SET, issuer, token), (holder, share_bps));
+        Ok(())
+    }
+
+    /// Set many holder shares in one call (max `bulk_shares` entries, per
+    /// `get_network_limits`). Later entries for the same holder overwrite earlier ones.
+    /// Fails with `InvalidShareBps` if any share exceeds 10000, or
+    /// `ShareAllocationExceeded` if the offering's summed holder shares would exceed its
+    /// revenue_share_bps afterwards; nothing is written in either case.
+    /// Emits `share_set` per entry.
+    pub fn set_holder_shares_bulk(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        shares: Vec<(Address, u32)>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+
+        issuer.require_auth_for_args((token.clone(), shares.clone()).into_val(&env));
+        Self::require_offering_open(&env, &token)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        if shares.len() > Self::get_network_limits(env.clone()).bulk_shares {
+            return Err(RevoraError::LimitReached);
+        }
+
+        // Validate the resulting aggregate before writing anything.
+        let mut pending: Map<Address, u32> = Map::new(&env);
+        let mut total = Self::share_total(&env, &token)?;
+        for (holder, share_bps) in shares.iter() {
+            if share_bps > 10_000 {
+                return Err(RevoraError::InvalidShareBps);
+            }
+            if share_bps > 0 {
+                Self::require_share_eligible(&env, &token, &holder)?;
+            }
+            let old_bps = pending.get(holder.clone()).unwrap_or_else(|| {
+                Self::get_holder_share(env.clone(), token.clone(), holder.clone())
+            });
+            total = total
+                .checked_sub(old_bps)
+                .and_then(|t| t.checked_add(share_bps))
+                .ok_or(RevoraError::InvalidState)?;
+            pending.set(holder, share_bps);
+        }
+        if total > Self::allocation_cap(&env, &issuer, &token) {
+            return Err(RevoraError::ShareAllocationExceeded);
+        }
+
+        // Apply decreases first so no intermediate total trips the allocation cap.
+        for (holder, _) in shares.iter() {
+            let share_bps = pending.get(holder.clone()).unwrap();
+            if share_bps < Self::get_holder_share(env.clone(), token.clone(), holder.clone()) {
+                Self::write_holder_share(&env, &token, &holder, share_bps)?;
+            }
+        }
+        for (holder, share_bps) in shares.iter() {
+            Self::write_holder_share(&env, &token, &holder, pending.get(holder.clone()).unwrap())?;
+            env.events().publish(
+                (EVENT_SHARE_SET, issuer.clone(), token.clone()),
+                (holder, share_bps),
+            );
+        }
+        Ok(())
+    }
+
+    // ── Capital raise (subscription phase) ─────────────────────
+
+    /// Open a subscription phase for an offering. Only the current issuer may call this;
+    /// one raise per offering. Subscriptions are paid in the offering's payout asset.
+    /// `min_raise` <= `target`; `allocation_bps` in 1..=10000; `deadline` in the future.
+    pub fn open_raise(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        target: i128,
+        min_raise: i128,
+        allocation_bps: u32,
+        deadline: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        Self::require_offering_open(&env, &token)?;
+
+        if target <= 0 || min_raise < 0 || min_raise > target {
+            return Err(RevoraError::InvalidAmount);
+        }
+        if allocation_bps == 0 || allocation_bps > 10_000 {
+            return Err(RevoraError::InvalidShareBps);
+        }
+        let key = DataKey::Raise(token.clone());
+        if deadline <= env.ledger().timestamp() || env.storage().persistent().has(&key) {
+            return Err(RevoraError::RaiseStateInvalid);
+        }
+
+        let raise = CapitalRaise {
+            target,
+            min_raise,
+            allocation_bps,
+            deadline,
+            total_raised: 0,
+            status: RaiseStatus::Open,
+        };
+        env.storage().persistent().set(&key, &raise);
+        env.events().publish(
+            (EVENT_RAISE_OPENED, issuer, token),
+            (target, min_raise, allocation_bps, deadline),
+        );
+        Ok(())
+    }
+
+    /// Subscribe `amount` of the payout asset to an open raise. Transfers funds from
+    /// `investor` to the contract. Fails with `RaiseCapExceeded` above the target.
+    pub fn subscribe(
+        env: Env,
+        investor: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        investor.require_auth();
+
+        if amount <= 0 {
+            return Err(RevoraError::InvalidAmount);
+        }
+        Self::require_share_eligible(&env, &token, &investor)?;
+        let key = DataKey::Raise(token.clone());
+        let mut raise: CapitalRaise = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RevoraError::RaiseStateInvalid)?;
+        if raise.status != RaiseStatus::Open || env.ledger().timestamp() >= raise.deadline {
+            return Err(RevoraError::RaiseStateInvalid);
+        }
+        let new_total = raise
+            .total_raised
+            .checked_add(amount)
+            .ok_or(RevoraError::RaiseCapExceeded)?;
+        if new_total > raise.target {
+            return Err(RevoraError::RaiseCapExceeded);
+        }
+
+        let issuer = Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        let offering = Self::get_offering(env.clone(), issuer, token.clone())
+            .ok_or(RevoraError::OfferingNotFound)?;
+        token::Client::new(&env, &offering.payout_asset).transfer(
+            &investor,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        raise.total_raised = new_total;
+        env.storage().persistent().set(&key, &raise);
+        let contrib_key = DataKey::RaiseContribution(token.clone(), investor.clone());
+        let prior: i128 = env.storage().persistent().get(&contrib_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&contrib_key, &(prior + amount));
+
+        env.events().publish(
+            (EVENT_RAISE_SUBSCRIBED, investor, token),
+            (amount, new_total),
+        );
+        Ok(())
+    }
+
+    /// Close the raise once the deadline has passed or the target is reached.
+    /// On success (`total_raised >= min_raise`) the raised funds are sent to the issuer;
+    /// otherwise the raise fails and subscribers can be refunded via `settle_subscription`.
+    pub fn finalize_raise(
+        env: Env,
+        issuer: Address,
+        token: Address,
+    ) -> Result<RaiseStatus, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let key = DataKey::Raise(token.clone());
+        let mut raise: CapitalRaise = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RevoraError::RaiseStateInvalid)?;
+        if raise.status != RaiseStatus::Open
+            || (env.ledger().timestamp() < raise.deadline && raise.total_raised < raise.target)
+        {
+            return Err(RevoraError::RaiseStateInvalid);
+        }
+
+        if raise.total_raised >= raise.min_raise {
+            raise.status = RaiseStatus::Succeeded;
+            if raise.total_raised > 0 {
+                let offering = Self::get_offering(env.clone(), issuer.clone(), token.clone())
+                    .ok_or(RevoraError::OfferingNotFound)?;
+                token::Client::new(&env, &offering.payout_asset).transfer(
+                    &env.current_contract_address(),
+                    &issuer,
+                    &raise.total_raised,
+                );
+            }
+        } else {
+            raise.status = RaiseStatus::Failed;
+        }
+        env.storage().persistent().set(&key, &raise);
+
+        env.events().publish(
+            (EVENT_RAISE_FINALIZED, issuer, token),
+            (raise.status, raise.total_raised),
+        );
+        Ok(raise.status)
+    }
+
+    /// Settle one subscriber after finalization. Anyone may call. On success credits
+    /// `contribution * allocation_bps / target` to the investor's holder share (capped at
+    /// 10000); on failure refunds the contribution. Returns the bps credited or amount refunded.
+    /// Fails with `ShareAllocationExceeded` if the credit would take the offering's summed
+    /// holder shares past its allocation cap, and with `OfferingClosed` when crediting a
+    /// closed offering; refunds of a failed raise still go through.
+    pub fn settle_subscription(
+        env: Env,
+        investor: Address,
+        token: Address,
+    ) -> Result<i128, RevoraError> {
+        Self::require_offering_not_paused(&env, &token)?;
+
+        let raise: CapitalRaise = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Raise(token.clone()))
+            .ok_or(RevoraError::RaiseStateInvalid)?;
+        let contrib_key = DataKey::RaiseContribution(token.clone(), investor.clone());
+        let contribution: i128 = env
+            .storage()
+            .persistent()
+            .get(&contrib_key)
+            .ok_or(RevoraError::RaiseStateInvalid)?;
+
+        let settled = match raise.status {
+            RaiseStatus::Open => return Err(RevoraError::RaiseStateInvalid),
+            RaiseStatus::Succeeded => {
+                Self::require_offering_open(&env, &token)?;
+                let bps = contribution * (raise.allocation_bps as i128) / raise.target;
+                Self::require_share_eligible(&env, &token, &investor)?;
+                let existing = Self::get_holder_share(env.clone(), token.clone(), investor.clone());
+                let new_share = core::cmp::min(existing as i128 + bps, 10_000) as u32;
+                Self::write_holder_share(&env, &token, &investor, new_share)?;
+                bps
+            }
+            RaiseStatus::Failed => {
+                let issuer =
+                    Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+                let offering = Self::get_offering(env.clone(), issuer, token.clone())
+                    .ok_or(RevoraError::OfferingNotFound)?;
+                token::Client::new(&env, &offering.payout_asset).transfer(
+                    &env.current_contract_address(),
+                    &investor,
+                    &contribution,
+                );
+                contribution
+            }
+        };
+        env.storage().persistent().remove(&contrib_key);
+
+        env.events().publish(
+            (EVENT_RAISE_SETTLED, investor, token),
+            (raise.status, settled),
+        );
+        Ok(settled)
+    }
+
+    // ── Secondary share transfers with right of first refusal ───
+
+    /// Configure whether holder shares are transferable and the ROFR window. Issuer only.
+    pub fn set_share_transfer_config(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        transferable: bool,
+        rofr_window_secs: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let config = ShareTransferConfig {
+            transferable,
+            rofr_window_secs,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::ShareTransferConfig(token.clone()), &config);
+        env.events().publish(
+            (EVENT_SHARE_XFER_CFG, issuer, token),
+            (transferable, rofr_window_secs),
+        );
+        Ok(())
+    }
+
+    /// Propose selling `share_bps` from `seller` to `buyer` for `price` in the offering's
+    /// payout asset. Both parties authorize; the buyer's payment is escrowed. During the
+    /// ROFR window the issuer or another holder may match via `exercise_rofr`.
+    /// Returns the transfer id.
+    pub fn propose_share_transfer(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        token: Address,
+        share_bps: u32,
+        price: i128,
+    ) -> Result<u32, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        seller.require_auth();
+        buyer.require_auth();
+
+        let config = Self::get_share_transfer_config(env.clone(), token.clone());
+        if !config.transferable {
+            return Err(RevoraError::SharesNotTransferable);
+        }
+        Self::open_share_transfer(&env, &seller, &buyer, &token, share_bps, price, &config)
+    }
+
+    /// Match a proposed transfer during its ROFR window. `matcher` must be the issuer or an
+    /// existing holder other than the seller. The matcher escrows the same price and the
+    /// original buyer is refunded. Only the first match wins.
+    pub fn exercise_rofr(
+        env: Env,
+        matcher: Address,
+        token: Address,
+        transfer_id: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        matcher.require_auth();
+
+        let key = DataKey::ShareTransfer(token.clone(), transfer_id);
+        let mut transfer: ShareTransfer = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RevoraError::InvalidState)?;
+        if transfer.matched
+            || transfer.completed
+            || transfer.cancelled
+            || env.ledger().timestamp() >= transfer.rofr_deadline
+        {
+            return Err(RevoraError::InvalidState);
+        }
+        let issuer = Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        let is_holder = Self::get_holder_share(env.clone(), token.clone(), matcher.clone()) > 0;
+        if matcher == transfer.seller || (matcher != issuer && !is_holder) {
+            return Err(RevoraError::NotAuthorized);
+        }
+        Self::require_share_eligible(&env, &token, &matcher)?;
+
+        if transfer.price > 0 {
+            let offering = Self::get_offering(env.clone(), issuer, token.clone())
+                .ok_or(RevoraError::OfferingNotFound)?;
+            let client = token::Client::new(&env, &offering.payout_asset);
+            let contract_addr = env.current_contract_address();
+            client.transfer(&matcher, &contract_addr, &transfer.price);
+            client.transfer(&contract_addr, &transfer.buyer, &transfer.price);
+        }
+
+        let displaced = transfer.buyer.clone();
+        transfer.buyer = matcher.clone();
+        transfer.matched = true;
+        env.storage().persistent().set(&key, &transfer);
+        env.events().publish(
+            (EVENT_SHARE_XFER_MATCHED, token, transfer_id),
+            (matcher, displaced),
+        );
+        Ok(())
+    }
+
+    /// Complete a transfer after its ROFR window. Anyone may call. Moves the shares to the
+    /// final buyer and releases the escrowed price to the seller. Fails with `OfferingClosed`
+    /// once the offering is closed; `cancel_share_transfer` then refunds the buyer.
+    pub fn complete_share_transfer(
+        env: Env,
+        token: Address,
+        transfer_id: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        Self::require_offering_open(&env, &token)?;
+
+        let key = DataKey::ShareTransfer(token.clone(), transfer_id);
+        let mut transfer: ShareTransfer = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RevoraError::InvalidState)?;
+        if transfer.completed
+            || transfer.cancelled
+            || env.ledger().timestamp() < transfer.rofr_deadline
+        {
+            return Err(RevoraError::InvalidState);
+        }
+
+        Self::release_share(&env, &token, &transfer.seller, transfer.share_bps);
+        Self::move_holder_share(
+            &env,
+            &token,
+            &transfer.seller,
+            &transfer.buyer,
+            transfer.share_bps,
+        )?;
+        if transfer.price > 0 {
+            let issuer =
+                Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+            let offering = Self::get_offering(env.clone(), issuer, token.clone())
+                .ok_or(RevoraError::OfferingNotFound)?;
+            token::Client::new(&env, &offering.payout_asset).transfer(
+                &env.current_contract_address(),
+                &transfer.seller,
+                &transfer.price,
+            );
+        }
+
+        transfer.completed = true;
+        env.storage().persistent().set(&key, &transfer);
+        env.events().publish(
+            (EVENT_SHARE_XFER_COMPLETED, token, transfer_id),
+            (
+                transfer.seller,
+                transfer.buyer,
+                transfer.share_bps,
+                transfer.price,
+            ),
+        );
+        Ok(())
+    }
+
+    /// Cancel a pending transfer that can no longer complete and refund the escrowed price
+    /// to the current buyer. Anyone may call. A transfer can no longer complete once the
+    /// offering is closed, the seller holds less than `share_bps`, or the buyer fails the
+    /// share eligibility checks or would exceed 10000 bps. Fails with `InvalidState` otherwise.
+    pub fn cancel_share_transfer(
+        env: Env,
+        token: Address,
+        transfer_id: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        let key = DataKey::ShareTransfer(token.clone(), transfer_id);
+        let mut transfer: ShareTransfer = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RevoraError::InvalidState)?;
+        if transfer.completed || transfer.cancelled {
+            return Err(RevoraError::InvalidState);
+        }
+        let seller_share =
+            Self::get_holder_share(env.clone(), token.clone(), transfer.seller.clone());
+        let buyer_share =
+            Self::get_holder_share(env.clone(), token.clone(), transfer.buyer.clone());
+        let stuck = Self::require_offering_open(&env, &token).is_err()
+            || seller_share < transfer.share_bps
+            || buyer_share + transfer.share_bps > 10_000
+            || Self::require_share_eligible(&env, &token, &transfer.buyer).is_err();
+        if !stuck {
+            return Err(RevoraError::InvalidState);
+        }
+
+        Self::release_share(&env, &token, &transfer.seller, transfer.share_bps);
+        if transfer.price > 0 {
+            let issuer =
+                Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+            let offering = Self::get_offering(env.clone(), issuer, token.clone())
+                .ok_or(RevoraError::OfferingNotFound)?;
+            token::Client::new(&env, &offering.payout_asset).transfer(
+                &env.current_contract_address(),
+                &transfer.buyer,
+                &transfer.price,
+            );
+        }
+
+        transfer.cancelled = true;
+        env.storage().persistent().set(&key, &transfer);
+        env.events().publish(
+            (EVENT_SHARE_XFER_CANCELLED, token, transfer_id),
+            (transfer.buyer, transfer.price),
+        );
+        Ok(())
+    }
+
+    // ── Escrowed share sales between holders ───────────────────
+
+    /// List `share_bps` for sale at `price` (payout asset). Offering must be transferable.
+    /// Shares stay with the seller until a buyer fills, but are committed to the listing:
+    /// `InvalidShareBps` if they exceed the seller's share not already committed to other
+    /// listings or pending transfers. Returns the listing id.
+    pub fn list_shares(
+        env: Env,
+        seller: Address,
+        token: Address,
+        share_bps: u32,
+        price: i128,
+    ) -> Result<u32, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        seller.require_auth();
+
+        if !Self::get_share_transfer_config(env.clone(), token.clone()).transferable {
+            return Err(RevoraError::SharesNotTransferable);
+        }
+        if price < 0 {
+            return Err(RevoraError::InvalidAmount);
+        }
+        Self::commit_share(&env, &token, &seller, share_bps)?;
+
+        let count_key = DataKey::ShareListingCount(token.clone());
+        let id: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let listing = ShareListing {
+            seller: seller.clone(),
+            share_bps,
+            price,
+            active: true,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::ShareListing(token.clone(), id), &listing);
+        env.storage().persistent().set(&count_key, &(id + 1));
+
+        env.events().publish(
+            (EVENT_LISTING_CREATED, token, id),
+            (seller, share_bps, price),
+        );
+        Ok(id)
+    }
+
+    /// Fill a listing. With no ROFR window the payment and share reassignment happen
+    /// atomically and `None` is returned. With a ROFR window the buyer's payment is
+    /// escrowed into a pending share transfer whose id is returned; finish it with
+    /// `complete_share_transfer`. Fails with `OfferingClosed` once the offering is closed.
+    pub fn fill_listing(
+        env: Env,
+        buyer: Address,
+        token: Address,
+        listing_id: u32,
+    ) -> Result<Option<u32>, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        Self::require_offering_open(&env, &token)?;
+        buyer.require_auth();
+
+        let key = DataKey::ShareListing(token.clone(), listing_id);
+        let mut listing: ShareListing = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RevoraError::InvalidState)?;
+        if !listing.active {
+            return Err(RevoraError::InvalidState);
+        }
+        let config = Self::get_share_transfer_config(env.clone(), token.clone());
+        if !config.transferable {
+            return Err(RevoraError::SharesNotTransferable);
+        }
+
+        // The pending transfer (or the share move) takes over the listing's commitment.
+        Self::release_share(&env, &token, &listing.seller, listing.share_bps);
+        let pending = if config.rofr_window_secs > 0 {
+            Some(Self::open_share_transfer(
+                &env,
+                &listing.seller,
+                &buyer,
+                &token,
+                listing.share_bps,
+                listing.price,
+                &config,
+            )?)
+        } else {
+            if buyer == listing.seller {
+                return Err(RevoraError::InvalidShareBps);
+            }
+            Self::move_holder_share(&env, &token, &listing.seller, &buyer, listing.share_bps)?;
+            if listing.price > 0 {
+                let issuer =
+                    Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+                let offering = Self::get_offering(env.clone(), issuer, token.clone())
+                    .ok_or(RevoraError::OfferingNotFound)?;
+                token::Client::new(&env, &offering.payout_asset).transfer(
+                    &buyer,
+                    &listing.seller,
+                    &listing.price,
+                );
+            }
+            None
+        };
+
+        listing.active = false;
+        env.storage().persistent().set(&key, &listing);
+        env.events().publish(
+            (EVENT_LISTING_FILLED, token, listing_id),
+            (buyer, listing.share_bps, listing.price, pending),
+        );
+        Ok(pending)
+    }
+
+    /// Cancel an active listing. Seller only.
+    pub fn cancel_listing(
+        env: Env,
+        seller: Address,
+        token: Address,
+        listing_id: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        seller.require_auth();
+
+        let key = DataKey::ShareListing(token.clone(), listing_id);
+        let mut listing: ShareListing = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RevoraError::InvalidState)?;
+        if listing.seller != seller {
+            return Err(RevoraError::NotAuthorized);
+        }
+        if !listing.active {
+            return Err(RevoraError::InvalidState);
+        }
+        Self::release_share(&env, &token, &seller, listing.share_bps);
+        listing.active = false;
+        env.storage().persistent().set(&key, &listing);
+        env.events()
+            .publish((EVENT_LISTING_CANCELLED, token, listing_id), seller);
+        Ok(())
+    }
+
+    // ── Secure issuer transfer (two-step flow) ─────────────────
+
+    /// Propose transferring issuer control of an offering to a new address.
+    /// Only the current issuer may call this. Initiates a two-step transfer.
+    pub fn propose_issuer_transfer(
+        env: Env,
+        token: Address,
+        new_issuer: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        // Get current issuer and verify offering exists
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+
+        // Only current issuer can propose transfer
+        current_issuer.require_auth();
+
+        // Check if transfer already pending
+        let pending_key = DataKey::PendingIssuerTransfer(token.clone());
+        if env.storage().persistent().has(&pending_key) {
+            return Err(RevoraError::IssuerTransferPending);
+        }
+
+        // Store pending transfer and let the proposed issuer discover it
+        let inbox_key = OfferingKey::TransferInbox(new_issuer.clone());
+        let mut inbox: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&inbox_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if inbox.len() >= MAX_TRANSFER_INBOX {
+            return Err(RevoraError::LimitReached);
+        }
+        inbox.push_back(token.clone());
+        env.storage().persistent().set(&inbox_key, &inbox);
+        env.storage().persistent().set(&pending_key, &new_issuer);
+        env.storage().persistent().set(
+            &OfferingKey::TransferProposedAt(token.clone()),
+            &env.ledger().timestamp(),
+        );
+        Self::index_pending_transfer(&env, &token);
+
+        env.events().publish(
+            (EVENT_ISSUER_TRANSFER_PROPOSED, token.clone()),
+            (current_issuer, new_issuer),
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending issuer transfer. Only the proposed new issuer may call this.
+    /// Completes the two-step transfer and grants full issuer control to the new address.
+    /// Authorization is bound to `(token, current_issuer)`. Fails with `ProposalExpired`
+    /// once the proposal is older than `ContractConfig.transfer_ttl_secs`.
+    pub fn accept_issuer_transfer(env: Env, token: Address) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        // Get pending transfer
+        let pending_key = DataKey::PendingIssuerTransfer(token.clone());
+        let new_issuer: Address = env
+            .storage()
+            .persistent()
+            .get(&pending_key)
+            .ok_or(RevoraError::NoTransferPending)?;
+
+        // Get current issuer
+        let old_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+
+        // Only the proposed new issuer can accept; the signature also binds the issuer
+        // being replaced so a stale authorization cannot complete a different transfer.
+        new_issuer.require_auth_for_args((token.clone(), old_issuer.clone()).into_val(&env));
+
+        if let Some(expires_at) = Self::get_issuer_transfer_expiry(env.clone(), token.clone()) {
+            if env.ledger().timestamp() >= expires_at {
+                return Err(RevoraError::ProposalExpired);
+            }
+        }
+
+        // Update the offering's issuer field in storage
+        // We need to find and update the offering
+        let offering = Self::get_offering(env.clone(), old_issuer.clone(), token.clone())
+            .ok_or(RevoraError::OfferingNotFound)?;
+
+        // Find the index of this offering
+        let index = Self::find_offering_index(&env, &old_issuer, &token)
+            .ok_or(RevoraError::OfferingNotFound)?;
+
+        // Update the offering with new issuer
+        let updated_offering = Offering {
+            issuer: new_issuer.clone(),
+            token: token.clone(),
+            revenue_share_bps: offering.revenue_share_bps,
+            payout_asset: offering.payout_asset,
+        };
+
+        // Remove from old issuer's storage
+        let old_item_key = DataKey::OfferItem(old_issuer.clone(), index);
+        env.storage().persistent().remove(&old_item_key);
+
+        // If this wasn't the last offering, move the last offering to fill the gap
+        let old_count = Self::get_offering_count(env.clone(), old_issuer.clone());
+        if index < old_count - 1 {
+            // Move the last offering to the removed index
+            let last_key = DataKey::OfferItem(old_issuer.clone(), old_count - 1);
+            let last_offering: Offering = env.storage().persistent().get(&last_key).unwrap();
+            env.storage()
+                .persistent()
+                .set(&old_item_key, &last_offering);
+            env.storage().persistent().remove(&last_key);
+            env.storage()
+                .persistent()
+                .set(&OfferingKey::ByToken(last_offering.token), &index);
+        }
+
+        // Decrement old issuer's count
+        let old_count_key = DataKey::OfferCount(old_issuer.clone());
+        env.storage()
+            .persistent()
+            .set(&old_count_key, &(old_count - 1));
+
+        // Add to new issuer's storage
+        let new_count = Self::get_offering_count(env.clone(), new_issuer.clone());
+        let new_item_key = DataKey::OfferItem(new_issuer.clone(), new_count);
+        env.storage()
+            .persistent()
+            .set(&new_item_key, &updated_offering);
+
+        // Increment new issuer's count
+        let new_count_key = DataKey::OfferCount(new_issuer.clone());
+        env.storage()
+            .persistent()
+            .set(&new_count_key, &(new_count + 1));
+        env.storage()
+            .persistent()
+            .set(&OfferingKey::ByToken(token.clone()), &new_count);
+
+        // Update reverse lookup
+        let issuer_lookup_key = DataKey::OfferingIssuer(token.clone());
+        env.storage()
+            .persistent()
+            .set(&issuer_lookup_key, &new_issuer);
+
+        // Clear pending transfer
+        Self::clear_pending_transfer(&env, &token, &new_issuer);
+
+        env.events().publish(
+            (EVENT_ISSUER_TRANSFER_ACCEPTED, token),
+            (old_issuer, new_issuer),
+        );
+
+        Ok(())
+    }
+
+    /// Cancel a pending issuer transfer. Only the current issuer may call this.
+    pub fn cancel_issuer_transfer(env: Env, token: Address) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        // Get current issuer
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+
+        // Only current issuer can cancel
+        current_issuer.require_auth();
+
+        // Check if transfer is pending
+        let pending_key = DataKey::PendingIssuerTransfer(token.clone());
+        let proposed_new_issuer: Address = env
+            .storage()
+            .persistent()
+            .get(&pending_key)
+            .ok_or(RevoraError::NoTransferPending)?;
+
+        // Clear pending transfer
+        Self::clear_pending_transfer(&env, &token, &proposed_new_issuer);
+
+        env.events().publish(
+            (EVENT_ISSUER_TRANSFER_CANCELLED, token),
+            (current_issuer, proposed_new_issuer),
+        );
+
+        Ok(())
+    }
+
+    /// Cancel a pending issuer transfer as its proposer (the current issuer) or the admin,
+    /// e.g. to clear an expired proposal. Emits `iss_canc` like `cancel_issuer_transfer`.
+    pub fn cancel_proposal(env: Env, caller: Address, token: Address) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        caller.require_auth();
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if caller != current_issuer {
+            Self::require_admin(&env, &caller)?;
+        }
+
+        let proposed_new_issuer: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingIssuerTransfer(token.clone()))
+            .ok_or(RevoraError::NoTransferPending)?;
+        Self::clear_pending_transfer(&env, &token, &proposed_new_issuer);
+
+        env.events().publish(
+            (EVENT_ISSUER_TRANSFER_CANCELLED, token),
+            (current_issuer, proposed_new_issuer),
+        );
+        Ok(())
+    }
+}
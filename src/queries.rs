@@ -0,0 +1,1952 @@
+//! Read-only entrypoints. Nothing here writes storage, publishes events or requires auth,
+//! so every view can be simulated freely.
+
+use super::*;
+
+#[contractimpl]
+impl RevoraRevenueShare {
+    /// Network profile chosen at `initialize_v3`, if any.
+    pub fn get_network_profile(env: Env) -> Option<NetworkProfile> {
+        env.storage().persistent().get(&DataKey::NetworkProfile)
+    }
+
+    /// Per-call limits in effect: those of the network profile, or Testnet's if unset,
+    /// with the admin's `page_limit` / `claim_periods` config overrides applied.
+    pub fn get_network_limits(env: Env) -> NetworkLimits {
+        let mut limits = Self::get_network_profile(env.clone())
+            .unwrap_or(NetworkProfile::Testnet)
+            .limits();
+        let store = env.storage().instance();
+        if let Some(n) = store.get(&LimitKey::PageLimit) {
+            limits.page_limit = n;
+        }
+        if let Some(n) = store.get(&LimitKey::ClaimPeriods) {
+            limits.claim_periods = n;
+        }
+        limits
+    }
+
+    /// When `freeze` ran, if the contract is frozen and the time was recorded.
+    pub fn get_frozen_at(env: Env) -> Option<u64> {
+        if !Self::is_frozen(env.clone()) {
+            return None;
+        }
+        env.storage().instance().get(&ClaimKey::FrozenAt)
+    }
+
+    /// Storage layout version: CONTRACT_VERSION for deployments initialized by current
+    /// code, 1 for older ones until `migrate` first runs.
+    pub fn get_version(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::ContractVersion)
+            .unwrap_or(1)
+    }
+
+    /// Code revisions installed by `upgrade`, oldest first. Empty until the first upgrade;
+    /// the code deployed initially is not listed.
+    pub fn get_version_history(env: Env) -> Vec<CodeRevision> {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::UpgradeHistory)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Return the highest completed initialization step (0 = not initialized).
+    pub fn get_init_version(env: Env) -> u32 {
+        if let Some(v) = env.storage().persistent().get(&DataKey::InitVersion) {
+            return v;
+        }
+        if Self::read_admin(&env).is_some() {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Dry run of `reconfigure`: run the same checks without auth and return the config
+    /// it would produce, so signers can verify a payload before approving it.
+    pub fn preview_reconfigure(
+        env: Env,
+        caller: Address,
+        update: ConfigUpdate,
+    ) -> Result<ContractConfig, RevoraError> {
+        Self::require_admin(&env, &caller)?;
+        Self::require_not_frozen(&env)?;
+        Self::validate_config_update(&env, &update)?;
+        let current = Self::get_config(env);
+        Ok(ContractConfig {
+            version: CONFIG_VERSION,
+            event_versioning: update.event_versioning.unwrap_or(current.event_versioning),
+            topics_hint: update.topics_hint.unwrap_or(current.topics_hint),
+            strike_threshold: update.strike_threshold.unwrap_or(current.strike_threshold),
+            max_pause_secs: update.max_pause_secs.unwrap_or(current.max_pause_secs),
+            sweep_grace_secs: update.sweep_grace_secs.unwrap_or(current.sweep_grace_secs),
+            platform_fee_bps: update.platform_fee_bps.unwrap_or(current.platform_fee_bps),
+            max_metadata_len: update.max_metadata_len.unwrap_or(current.max_metadata_len),
+            transfer_ttl_secs: update
+                .transfer_ttl_secs
+                .unwrap_or(current.transfer_ttl_secs),
+            verify_offering_token: update
+                .verify_offering_token
+                .unwrap_or(current.verify_offering_token),
+            min_claim_delay_secs: update
+                .min_claim_delay_secs
+                .unwrap_or(current.min_claim_delay_secs),
+            max_claim_delay_secs: update
+                .max_claim_delay_secs
+                .unwrap_or(current.max_claim_delay_secs),
+            delinquency_periods: update
+                .delinquency_periods
+                .unwrap_or(current.delinquency_periods),
+            reject_underpayment: update
+                .reject_underpayment
+                .unwrap_or(current.reject_underpayment),
+            page_limit: update.page_limit.unwrap_or(current.page_limit),
+            claim_periods: update.claim_periods.unwrap_or(current.claim_periods),
+            max_offerings_per_issuer: update
+                .max_offerings_per_issuer
+                .unwrap_or(current.max_offerings_per_issuer),
+            max_total_offerings: update
+                .max_total_offerings
+                .unwrap_or(current.max_total_offerings),
+        })
+    }
+
+    /// Return the effective contract config, filling unset fields with their defaults.
+    pub fn get_config(env: Env) -> ContractConfig {
+        ContractConfig {
+            version: env
+                .storage()
+                .persistent()
+                .get(&DataKey::ConfigVersion)
+                .unwrap_or(0),
+            event_versioning: Self::is_event_versioning_enabled(env.clone()),
+            topics_hint: env
+                .storage()
+                .persistent()
+                .get(&DataKey::TopicsHintEnabled)
+                .unwrap_or(false),
+            strike_threshold: env
+                .storage()
+                .persistent()
+                .get(&DataKey::StrikeThreshold)
+                .unwrap_or(0),
+            max_pause_secs: env
+                .storage()
+                .persistent()
+                .get(&DataKey::MaxPauseSecs)
+                .unwrap_or(0),
+            sweep_grace_secs: env
+                .storage()
+                .persistent()
+                .get(&DataKey::SweepGraceSecs)
+                .unwrap_or(0),
+            platform_fee_bps: env.storage().persistent().get(&FeeKey::Bps).unwrap_or(0),
+            max_metadata_len: env
+                .storage()
+                .persistent()
+                .get(&MetadataKey::MaxLength)
+                .unwrap_or(MAX_METADATA_LENGTH),
+            transfer_ttl_secs: env
+                .storage()
+                .persistent()
+                .get(&OfferingKey::TransferTtlSecs)
+                .unwrap_or(0),
+            verify_offering_token: env.storage().persistent().has(&OfferingKey::VerifyToken),
+            min_claim_delay_secs: Self::claim_delay_bounds(&env).0,
+            max_claim_delay_secs: Self::claim_delay_bounds(&env).1,
+            delinquency_periods: env
+                .storage()
+                .persistent()
+                .get(&OfferingKey::DelinquencyPeriods)
+                .unwrap_or(DEFAULT_DELINQUENCY_PERIODS),
+            reject_underpayment: env
+                .storage()
+                .persistent()
+                .has(&EscrowKey::RejectUnderpayment),
+            page_limit: env
+                .storage()
+                .instance()
+                .get(&LimitKey::PageLimit)
+                .unwrap_or(0),
+            claim_periods: env
+                .storage()
+                .instance()
+                .get(&LimitKey::ClaimPeriods)
+                .unwrap_or(0),
+            max_offerings_per_issuer: env
+                .storage()
+                .persistent()
+                .get(&OfferingKey::MaxPerIssuer)
+                .unwrap_or(0),
+            max_total_offerings: env
+                .storage()
+                .persistent()
+                .get(&OfferingKey::MaxTotal)
+                .unwrap_or(0),
+        }
+    }
+
+    /// Roles held by `addr`, in `Role` code order.
+    pub fn get_roles(env: Env, addr: Address) -> Vec<Role> {
+        let mut roles = Vec::new(&env);
+        for role in ALL_ROLES {
+            if Self::has_role(env.clone(), role, addr.clone()) {
+                roles.push_back(role);
+            }
+        }
+        roles
+    }
+
+    /// Whether `addr` holds `role`.
+    pub fn has_role(env: Env, role: Role, addr: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&AdminKey::RoleMember(role, addr))
+    }
+
+    /// Return the safety addresses. Deployments that predate `SafetySet` fall back to the
+    /// single `Safety` entry.
+    pub fn get_safety_addresses(env: Env) -> Vec<Address> {
+        if let Some(set) = env.storage().persistent().get(&DataKey::SafetySet) {
+            return set;
+        }
+        let mut set = Vec::new(&env);
+        if let Some(s) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Address>(&DataKey::Safety)
+        {
+            set.push_back(s);
+        }
+        set
+    }
+
+    /// Query the paused state of the contract. A pause older than the configured
+    /// `max_pause_secs` has lapsed and reads as unpaused.
+    pub fn is_paused(env: Env) -> bool {
+        if !Self::read_flag(&env, DataKey::Paused) {
+            return false;
+        }
+        let max_secs: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MaxPauseSecs)
+            .unwrap_or(0);
+        if max_secs == 0 {
+            return true;
+        }
+        let paused_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PausedAt)
+            .unwrap_or(0);
+        env.ledger().timestamp() < paused_at.saturating_add(max_secs)
+    }
+
+    /// Invariant that froze the offering, or `None` if it is not frozen.
+    pub fn get_invariant_freeze(env: Env, token: Address) -> Option<InvariantViolation> {
+        env.storage()
+            .persistent()
+            .get(&OfferingKey::InvariantFrozen(token))
+    }
+
+    /// Whether an offering is paused on its own (ignores the global pause).
+    pub fn is_offering_paused(env: Env, token: Address) -> bool {
+        env.storage().persistent().has(&OfferingKey::Paused(token))
+    }
+
+    /// Period calendar for `token`, if any.
+    pub fn get_period_schedule(env: Env, token: Address) -> Option<PeriodSchedule> {
+        env.storage()
+            .persistent()
+            .get(&OfferingKey::PeriodSchedule(token))
+    }
+
+    /// Period the schedule places `timestamp` in. `None` without a schedule or before its
+    /// start.
+    pub fn get_expected_period(env: Env, token: Address, timestamp: u64) -> Option<u64> {
+        let schedule = Self::get_period_schedule(env, token)?;
+        if timestamp < schedule.start {
+            return None;
+        }
+        Some((timestamp - schedule.start) / schedule.interval_secs + 1)
+    }
+
+    /// Scheduled periods of `token` that have ended without a deposit, oldest first. Only
+    /// the latest 100 ended periods are checked; empty without a schedule.
+    pub fn get_missed_periods(env: Env, token: Address) -> Vec<u64> {
+        let mut missed = Vec::new(&env);
+        let Some(current) =
+            Self::get_expected_period(env.clone(), token.clone(), env.ledger().timestamp())
+        else {
+            return missed;
+        };
+        let first = current.saturating_sub(MAX_MISSED_SCAN).max(1);
+        for period_id in first..current {
+            if !env
+                .storage()
+                .persistent()
+                .has(&DataKey::PeriodRevenue(token.clone(), period_id))
+            {
+                missed.push_back(period_id);
+            }
+        }
+        missed
+    }
+
+    /// Delinquency flag of `token`, if set and not yet cleared by `flag_delinquent`.
+    pub fn get_delinquency(env: Env, token: Address) -> Option<Delinquency> {
+        env.storage()
+            .persistent()
+            .get(&OfferingKey::Delinquent(token))
+    }
+
+    /// Offering ID assigned to `token` at registration, if any.
+    pub fn get_offering_id(env: Env, token: Address) -> Option<u64> {
+        env.storage().persistent().get(&OfferingKey::Id(token))
+    }
+
+    /// Offering with ID `id`, under its current issuer.
+    pub fn get_offering_by_id(env: Env, id: u64) -> Option<Offering> {
+        let token: Address = env.storage().persistent().get(&OfferingKey::ById(id))?;
+        let issuer = Self::get_current_issuer(&env, &token)?;
+        Self::get_offering(env, issuer, token)
+    }
+
+    /// Number of offering IDs handed out platform-wide. IDs run from 1 to this count; a
+    /// re-registered token keeps its first ID.
+    pub fn get_total_offering_count(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&OfferingKey::LastId)
+            .unwrap_or(0)
+    }
+
+    /// Page of all offerings platform-wide, by offering ID, each under its current issuer.
+    /// Same cursor as `export_offerings_page`: `start` is an offering ID and the returned
+    /// cursor is `None` after the last one. Limit capped at the network page limit. Issuer
+    /// transfers need no re-indexing since the ID index holds the token, not the issuer.
+    pub fn get_all_offerings_page(
+        env: Env,
+        start: u64,
+        limit: u32,
+    ) -> (Vec<Offering>, Option<u64>) {
+        let last_id = Self::get_total_offering_count(env.clone());
+        let limit = Self::export_limit(&env, limit);
+        let mut results = Vec::new(&env);
+        let start = start.max(1);
+        if start > last_id {
+            return (results, None);
+        }
+
+        let end = core::cmp::min(start.saturating_add(limit as u64), last_id + 1);
+        for id in start..end {
+            if let Some(offering) = Self::get_offering_by_id(env.clone(), id) {
+                results.push_back(offering);
+            }
+        }
+
+        let next_cursor = if end <= last_id { Some(end) } else { None };
+        (results, next_cursor)
+    }
+
+    /// Fetch a single offering by issuer and token.
+    pub fn get_offering(env: Env, issuer: Address, token: Address) -> Option<Offering> {
+        let index = Self::find_offering_index(&env, &issuer, &token)?;
+        env.storage()
+            .persistent()
+            .get(&DataKey::OfferItem(issuer, index))
+    }
+
+    /// Lifecycle status of an offering, or `None` if it does not exist. A frozen contract
+    /// reports `Frozen` for every offering.
+    pub fn get_offering_status(env: Env, token: Address) -> Option<OfferingStatus> {
+        Self::get_current_issuer(&env, &token)?;
+        if Self::is_frozen(env.clone()) {
+            Some(OfferingStatus::Frozen)
+        } else if env
+            .storage()
+            .persistent()
+            .has(&OfferingKey::Closed(token.clone()))
+        {
+            Some(OfferingStatus::Closed)
+        } else if Self::is_paused(env.clone()) || Self::is_offering_paused(env, token) {
+            Some(OfferingStatus::Paused)
+        } else {
+            Some(OfferingStatus::Active)
+        }
+    }
+
+    /// Offering record with its status and compliance settings, or `None` if it does not
+    /// exist.
+    pub fn get_offering_details(env: Env, token: Address) -> Option<OfferingDetails> {
+        let issuer = Self::get_current_issuer(&env, &token)?;
+        Some(OfferingDetails {
+            offering: Self::get_offering(env.clone(), issuer, token.clone())?,
+            status: Self::get_offering_status(env.clone(), token.clone())?,
+            compliance_officer: Self::get_compliance_officer(env.clone(), token.clone()),
+            blacklist_locked: Self::is_blacklist_locked(env.clone(), token.clone()),
+            accounting_currency: Self::get_accounting_currency(env, token),
+        })
+    }
+
+    /// Accounting currency of `token`, if tagged.
+    pub fn get_accounting_currency(env: Env, token: Address) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&OfferingKey::Currency(token))
+    }
+
+    /// Metadata for an offering, if the issuer set any.
+    pub fn get_offering_metadata(env: Env, token: Address) -> Option<OfferingMetadata> {
+        env.storage()
+            .persistent()
+            .get(&MetadataKey::Offering(token))
+    }
+
+    /// Number of `set_offering_metadata` calls recorded for an offering. The latest version
+    /// is `count - 1`; metadata set before history was kept has no version.
+    pub fn get_metadata_version_count(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&MetadataKey::VersionCount(token))
+            .unwrap_or(0)
+    }
+
+    /// Metadata as set by the `version`-th `set_offering_metadata` call (from 0), with when
+    /// it was set.
+    pub fn get_metadata_at_version(
+        env: Env,
+        token: Address,
+        version: u32,
+    ) -> Option<MetadataRevision> {
+        env.storage()
+            .persistent()
+            .get(&MetadataKey::Version(token, version))
+    }
+
+    /// Current structured offering metadata, if the issuer set any.
+    pub fn get_offering_metadata_v2(env: Env, token: Address) -> Option<OfferingMetadataV2> {
+        env.storage().persistent().get(&MetadataKey::V2(token))
+    }
+
+    /// Metadata for `locale`, falling back to the offering's default metadata when the
+    /// locale has none.
+    pub fn get_localized_metadata(
+        env: Env,
+        token: Address,
+        locale: Symbol,
+    ) -> Option<OfferingMetadata> {
+        env.storage()
+            .persistent()
+            .get(&MetadataKey::Localized(token.clone(), locale))
+            .or_else(|| Self::get_offering_metadata(env, token))
+    }
+
+    /// Locales with localized metadata for an offering, in first-set order.
+    pub fn get_metadata_locales(env: Env, token: Address) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&MetadataKey::Locales(token))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Page of `issuer`'s offering tokens. Same cursor and limit as `get_offerings_page`:
+    /// start at 0 and pass the returned cursor until it is `None`.
+    pub fn list_offerings(
+        env: Env,
+        issuer: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<Address>, Option<u32>) {
+        let (page, next_cursor) = Self::get_offerings_page(env.clone(), issuer, start, limit);
+        let mut tokens = Vec::new(&env);
+        for offering in page.iter() {
+            tokens.push_back(offering.token);
+        }
+        (tokens, next_cursor)
+    }
+
+    /// Return the total number of offerings registered by `issuer`.
+    pub fn get_offering_count(env: Env, issuer: Address) -> u32 {
+        let count_key = DataKey::OfferCount(issuer);
+        env.storage().persistent().get(&count_key).unwrap_or(0)
+    }
+
+    /// Return a page of offerings for `issuer`. Limit capped at the network page limit (20; 10 on Mainnet).
+    pub fn get_offerings_page(
+        env: Env,
+        issuer: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<Offering>, Option<u32>) {
+        let count = Self::get_offering_count(env.clone(), issuer.clone());
+
+        let page_limit = Self::get_network_limits(env.clone()).page_limit;
+        let effective_limit = if limit == 0 || limit > page_limit {
+            page_limit
+        } else {
+            limit
+        };
+
+        if start >= count {
+            return (Vec::new(&env), None);
+        }
+
+        let end = core::cmp::min(start + effective_limit, count);
+        let mut results = Vec::new(&env);
+
+        for i in start..end {
+            let item_key = DataKey::OfferItem(issuer.clone(), i);
+            let offering: Offering = env.storage().persistent().get(&item_key).unwrap();
+            results.push_back(offering);
+        }
+
+        let next_cursor = if end < count { Some(end) } else { None };
+        (results, next_cursor)
+    }
+
+    /// Compliance officer managing an offering's blacklist, if one is set.
+    pub fn get_compliance_officer(env: Env, token: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::Officer(token))
+    }
+
+    /// Whether blacklist additions are permanently disabled for an offering.
+    pub fn is_blacklist_locked(env: Env, token: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&ComplianceKey::BlacklistLocked(token))
+    }
+
+    /// Returns `true` if `investor` is blacklisted for `token`'s offering.
+    pub fn is_blacklisted(env: Env, token: Address, investor: Address) -> bool {
+        let key = DataKey::Blacklist(token);
+        env.storage()
+            .persistent()
+            .get::<DataKey, Map<Address, bool>>(&key)
+            .map(|m| m.get(investor).unwrap_or(false))
+            .unwrap_or(false)
+    }
+
+    /// Return all blacklisted addresses for `token`'s offering.
+    pub fn get_blacklist(env: Env, token: Address) -> Vec<Address> {
+        let key = DataKey::Blacklist(token);
+        env.storage()
+            .persistent()
+            .get::<DataKey, Map<Address, bool>>(&key)
+            .map(|m| m.keys())
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Whether whitelist enforcement is on for `token`.
+    pub fn is_whitelist_enabled(env: Env, token: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&ComplianceKey::WhitelistEnabled(token))
+    }
+
+    /// Returns `true` if `investor` is on `token`'s whitelist (whether or not it is
+    /// enforced).
+    pub fn is_whitelisted(env: Env, token: Address, investor: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get::<ComplianceKey, Map<Address, bool>>(&ComplianceKey::Whitelist(token))
+            .map(|m| m.get(investor).unwrap_or(false))
+            .unwrap_or(false)
+    }
+
+    /// Return a page of `token`'s whitelist in address order, the same order as
+    /// `get_blacklist`. Limit capped at the network page limit.
+    pub fn get_whitelist_page(
+        env: Env,
+        token: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<Address>, Option<u32>) {
+        let keys = env
+            .storage()
+            .persistent()
+            .get::<ComplianceKey, Map<Address, bool>>(&ComplianceKey::Whitelist(token))
+            .map(|m| m.keys())
+            .unwrap_or_else(|| Vec::new(&env));
+        let count = keys.len();
+        if start >= count {
+            return (Vec::new(&env), None);
+        }
+        let page_limit = Self::get_network_limits(env.clone()).page_limit;
+        let effective_limit = if limit == 0 || limit > page_limit {
+            page_limit
+        } else {
+            limit
+        };
+        let end = core::cmp::min(start + effective_limit, count);
+        let next_cursor = if end < count { Some(end) } else { None };
+        (keys.slice(start..end), next_cursor)
+    }
+
+    /// Whether `investor` is on the platform-wide blacklist.
+    pub fn is_globally_blacklisted(env: Env, investor: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&ComplianceKey::GlobalBlacklist(investor))
+    }
+
+    /// Timestamp until which `holder`'s claims on `token` are held (0 = none).
+    pub fn get_holder_lockup(env: Env, token: Address, holder: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::Lockup(token, holder))
+            .unwrap_or(0)
+    }
+
+    /// KYC attestation registry, if set.
+    pub fn get_kyc_registry(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&ComplianceKey::KycRegistry)
+    }
+
+    /// Whether `token` requires a KYC attestation.
+    pub fn is_kyc_required(env: Env, token: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&ComplianceKey::KycRequired(token))
+    }
+
+    /// Investor restrictions for `token`, if any.
+    pub fn get_offering_restrictions(env: Env, token: Address) -> Option<Restrictions> {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::Restrictions(token))
+    }
+
+    /// `holder`'s compliance profile on `token`, if recorded.
+    pub fn get_holder_profile(env: Env, token: Address, holder: Address) -> Option<HolderProfile> {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::HolderProfile(token, holder))
+    }
+
+    /// Terms hash anchored for `token`, if any.
+    pub fn get_offering_terms(env: Env, token: Address) -> Option<OfferingTerms> {
+        env.storage().persistent().get(&ComplianceKey::Terms(token))
+    }
+
+    /// `holder`'s latest terms acknowledgement on `token`; compare its hash with
+    /// `get_offering_terms` to see whether it covers the current version.
+    pub fn get_terms_acknowledgement(
+        env: Env,
+        token: Address,
+        holder: Address,
+    ) -> Option<TermsAcknowledgement> {
+        env.storage()
+            .persistent()
+            .get(&ComplianceKey::TermsAck(token, holder))
+    }
+
+    /// Get concentration limit config for an offering.
+    pub fn get_concentration_limit(
+        env: Env,
+        issuer: Address,
+        token: Address,
+    ) -> Option<ConcentrationLimitConfig> {
+        let key = DataKey::ConcentrationLimit(issuer, token);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Get last reported concentration in bps for an offering.
+    pub fn get_current_concentration(env: Env, issuer: Address, token: Address) -> Option<u32> {
+        let key = DataKey::CurrentConcentration(issuer, token);
+        env.storage().persistent().get(&key)
+    }
+
+    // ── Audit log summary (#34) ────────────────────────────────
+
+    /// Get per-offering audit summary (total revenue and report count).
+    pub fn get_audit_summary(env: Env, issuer: Address, token: Address) -> Option<AuditSummary> {
+        let key = DataKey::AuditSummary(issuer, token);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Auditors approved for `token`, in added order.
+    pub fn get_auditors(env: Env, token: Address) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&AuditKey::Auditors(token))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Attestations for `token`'s `period_id` report, one per auditor in first-attested order.
+    pub fn get_attestations(env: Env, token: Address, period_id: u64) -> Vec<Attestation> {
+        env.storage()
+            .persistent()
+            .get(&AuditKey::Attestations(token, period_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get rounding mode for an offering. Defaults to Truncation if not set.
+    pub fn get_rounding_mode(env: Env, issuer: Address, token: Address) -> RoundingMode {
+        let key = DataKey::RoundingMode(issuer, token);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(RoundingMode::Truncation)
+    }
+
+    /// Compute share of `amount` at `revenue_share_bps` using the given rounding mode.
+    /// Guarantees: result between 0 and amount (inclusive); no loss of funds when summing shares if caller uses same mode.
+    /// Same as `math::compute_share`.
+    pub fn compute_share(
+        _env: Env,
+        amount: i128,
+        revenue_share_bps: u32,
+        mode: RoundingMode,
+    ) -> i128 {
+        math::compute_share(amount, revenue_share_bps, mode)
+    }
+
+    /// Dry run of `set_offering_fee_bps`: run the same checks without auth and return the
+    /// fee in bps the offering's deposits would pay afterwards.
+    pub fn preview_offering_fee_bps(
+        env: Env,
+        admin: Address,
+        token: Address,
+        fee_bps: Option<u32>,
+    ) -> Result<u32, RevoraError> {
+        Self::require_admin_or_role(&env, &admin, Role::FeeManager)?;
+        Self::require_not_frozen(&env)?;
+        Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        match fee_bps {
+            Some(bps) if bps > MAX_PLATFORM_FEE_BPS => Err(RevoraError::InvalidAmount),
+            Some(bps) => Ok(bps),
+            None => Ok(env.storage().persistent().get(&FeeKey::Bps).unwrap_or(0)),
+        }
+    }
+
+    /// Platform fee in bps applied to the offering's deposits: its override if set,
+    /// else the global `platform_fee_bps`.
+    pub fn get_offering_fee_bps(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&FeeKey::OfferingBps(token))
+            .unwrap_or_else(|| env.storage().persistent().get(&FeeKey::Bps).unwrap_or(0))
+    }
+
+    /// Platform fees accrued in `payment_token` and not yet withdrawn.
+    pub fn get_accrued_fees(env: Env, payment_token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&FeeKey::Accrued(payment_token))
+            .unwrap_or(0)
+    }
+
+    /// Referrer of an offering and its cut of the platform fee in bps, if set.
+    pub fn get_referrer(env: Env, token: Address) -> Option<(Address, u32)> {
+        env.storage().persistent().get(&FeeKey::Referrer(token))
+    }
+
+    /// Referral fees accrued to `referrer` in `payment_token` and not yet claimed.
+    pub fn get_referral_fees(env: Env, referrer: Address, payment_token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&FeeKey::ReferralAccrued(referrer, payment_token))
+            .unwrap_or(0)
+    }
+
+    /// Sum of all holder shares (bps) currently allocated for an offering.
+    pub fn get_total_allocated_bps(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TotalShareBps(token))
+            .unwrap_or(0)
+    }
+
+    /// Return a holder's share in basis points for an offering (0 if unset).
+    pub fn get_holder_share(env: Env, token: Address, holder: Address) -> u32 {
+        let key = DataKey::HolderShare(token, holder);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Number of holders ever assigned a share for an offering (the holder index size).
+    /// Holders whose share was later set to 0 stay in the index.
+    pub fn get_holder_count(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::HolderCount(token))
+            .unwrap_or(0)
+    }
+
+    /// Page through the holder index as `(holder, share_bps)` in first-assignment order.
+    /// `limit` capped at the network page limit; returns the next cursor like `get_offerings_page`.
+    pub fn get_holders_page(
+        env: Env,
+        token: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<(Address, u32)>, Option<u32>) {
+        let count = Self::get_holder_count(env.clone(), token.clone());
+
+        let page_limit = Self::get_network_limits(env.clone()).page_limit;
+        let effective_limit = if limit == 0 || limit > page_limit {
+            page_limit
+        } else {
+            limit
+        };
+
+        if start >= count {
+            return (Vec::new(&env), None);
+        }
+
+        let end = core::cmp::min(start + effective_limit, count);
+        let mut results = Vec::new(&env);
+
+        for i in start..end {
+            let holder: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::HolderAt(token.clone(), i))
+                .unwrap();
+            let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+            results.push_back((holder, share_bps));
+        }
+
+        let next_cursor = if end < count { Some(end) } else { None };
+        (results, next_cursor)
+    }
+
+    // ── Router compatibility ───────────────────────────────────
+
+    /// Amount `holder` could claim right now on `token`. Standard router view;
+    /// same result as `get_claimable`.
+    pub fn claimable_of(env: Env, token: Address, holder: Address) -> i128 {
+        Self::get_claimable(env, token, holder)
+    }
+
+    /// Return the holder's payout address override for `token`, if any.
+    pub fn get_payout_address(env: Env, token: Address, holder: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&ClaimKey::PayoutAddress(token, holder))
+    }
+
+    /// Donation split applied to `holder`'s claims on `token`, if any.
+    pub fn get_donation(env: Env, token: Address, holder: Address) -> Option<DonationSplit> {
+        env.storage()
+            .persistent()
+            .get(&ClaimKey::Donation(token, holder))
+    }
+
+    /// Payment-token amount that buys 1 bps of share (0 = reinvestment off).
+    pub fn get_reinvest_rate(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ClaimKey::ReinvestRate(token))
+            .unwrap_or(0)
+    }
+
+    /// Whether `holder` reinvests claims on `token`.
+    pub fn is_auto_reinvest(env: Env, token: Address, holder: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&ClaimKey::AutoReinvest(token, holder))
+    }
+
+    /// Sub-account breakdown of `custodian`'s share of `token` (empty if none).
+    pub fn get_sub_accounts(env: Env, token: Address, custodian: Address) -> Vec<SubAccount> {
+        env.storage()
+            .persistent()
+            .get(&ClaimKey::SubAccounts(token, custodian))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Split `amount` across `custodian`'s sub-accounts as `(id, amount)` pairs, the way
+    /// claims attribute payouts. Rounding dust goes to the last sub-account, so the parts
+    /// always sum to `amount`. Empty if the custodian has no breakdown.
+    pub fn get_sub_account_allocation(
+        env: Env,
+        token: Address,
+        custodian: Address,
+        amount: i128,
+    ) -> Vec<(u64, i128)> {
+        let accounts = Self::get_sub_accounts(env.clone(), token, custodian);
+        Self::allocate_sub_accounts(&env, &accounts, amount)
+    }
+
+    /// Stream duration in seconds for `token` (0 = streaming off).
+    pub fn get_stream_duration(env: Env, token: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&ClaimKey::StreamSecs(token))
+            .unwrap_or(0)
+    }
+
+    /// Amount `holder` could claim right now: like `get_claimable`, but counting only the
+    /// unlocked part of periods that are still streaming.
+    pub fn get_streamable(env: Env, token: Address, holder: Address) -> i128 {
+        let stream_secs = Self::get_stream_duration(env.clone(), token.clone());
+        Self::pending_payout(&env, &token, &holder, stream_secs)
+    }
+
+    /// Claim order configured for `token`.
+    pub fn get_claim_order(env: Env, token: Address) -> ClaimOrder {
+        env.storage()
+            .persistent()
+            .get(&ClaimKey::Order(token))
+            .unwrap_or(ClaimOrder::OldestFirst)
+    }
+
+    /// Order `holder`'s next claim on `token` will use: `OldestFirst` or `NewestFirst`.
+    pub fn get_holder_claim_order(env: Env, token: Address, holder: Address) -> ClaimOrder {
+        match Self::get_claim_order(env.clone(), token.clone()) {
+            ClaimOrder::HolderChoice
+                if env
+                    .storage()
+                    .persistent()
+                    .has(&ClaimKey::NewestFirst(token, holder)) =>
+            {
+                ClaimOrder::NewestFirst
+            }
+            ClaimOrder::HolderChoice => ClaimOrder::OldestFirst,
+            order => order,
+        }
+    }
+
+    /// Return the holder's claim delegate for `token`, if any.
+    pub fn get_claim_delegate(env: Env, token: Address, holder: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&ClaimKey::Delegate(token, holder))
+    }
+
+    /// Read-only: estimate the resources `claim(holder, token, max_periods)` would use now,
+    /// so wallets can pick a `max_periods` that fits Soroban limits. Mirrors the reads in
+    /// `do_claim`; keep the two in step. Returns all zeros except the fixed reads when the
+    /// claim would fail.
+    pub fn estimate_claim_cost(
+        env: Env,
+        token: Address,
+        holder: Address,
+        max_periods: u32,
+    ) -> ClaimCostEstimate {
+        // PayoutAddress, Paused, InvariantFrozen, OfferingPaused, GlobalBlacklist, Blacklist,
+        // Lockup, KycRequired, Restrictions, WhitelistEnabled, HolderShare, LiveSupply, PeriodCount, LastClaimedIdx,
+        // NetworkProfile, ClaimOrder, DelaySchedule, Bonus, StreamSecs.
+        let mut est = ClaimCostEstimate {
+            periods: 0,
+            storage_reads: 19,
+            storage_writes: 0,
+            transfers: 0,
+        };
+        if Self::is_globally_blacklisted(env.clone(), holder.clone()) {
+            est.storage_reads = 5;
+            return est;
+        }
+        if Self::is_blacklisted(env.clone(), token.clone(), holder.clone()) {
+            est.storage_reads = 6;
+            return est;
+        }
+        if env.ledger().timestamp()
+            < Self::get_holder_lockup(env.clone(), token.clone(), holder.clone())
+        {
+            est.storage_reads = 7;
+            return est;
+        }
+        let kyc_required = Self::is_kyc_required(env.clone(), token.clone());
+        if kyc_required {
+            // KycRegistry read.
+            est.storage_reads += 1;
+            if !Self::has_kyc_attestation(&env, &holder) {
+                est.storage_reads = 9;
+                return est;
+            }
+        }
+        let restricted = Self::get_offering_restrictions(env.clone(), token.clone()).is_some();
+        if restricted {
+            // HolderProfile read.
+            est.storage_reads += 1;
+            if Self::require_restrictions_met(&env, &token, &holder).is_err() {
+                est.storage_reads = 10 + kyc_required as u32;
+                return est;
+            }
+        }
+        let whitelist_enabled = Self::is_whitelist_enabled(env.clone(), token.clone());
+        if whitelist_enabled {
+            // Whitelist read.
+            est.storage_reads += 1;
+            if !Self::is_whitelisted(env.clone(), token.clone(), holder.clone()) {
+                est.storage_reads = 11 + kyc_required as u32 + restricted as u32;
+                return est;
+            }
+        }
+        let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+        let balance = Self::live_balance(&env, &token, &holder);
+        if share_bps == 0 && balance.unwrap_or(0) == 0 {
+            est.storage_reads =
+                12 + kyc_required as u32 + restricted as u32 + whitelist_enabled as u32;
+            return est;
+        }
+
+        let period_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodCount(token.clone()))
+            .unwrap_or(0);
+        let start_idx: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastClaimedIdx(token.clone(), holder.clone()))
+            .unwrap_or(0);
+        let claim_cap = Self::get_network_limits(env.clone()).claim_periods;
+        let effective_max = if max_periods == 0 || max_periods > claim_cap {
+            claim_cap
+        } else {
+            max_periods
+        };
+        // The holder's preference under HolderChoice, and the settled band when newest
+        // first.
+        let mut walk = Self::claim_walk(&env, &token, &holder, start_idx, period_count);
+        if Self::get_claim_order(env.clone(), token.clone()) == ClaimOrder::HolderChoice {
+            est.storage_reads += 1;
+        }
+        if walk.newest {
+            est.storage_reads += 1;
+        }
+        let steps = if start_idx < period_count {
+            core::cmp::min(walk.len(), effective_max)
+        } else {
+            0
+        };
+        if !env
+            .storage()
+            .persistent()
+            .has(&ClaimKey::DelaySchedule(token.clone()))
+        {
+            // ClaimDelaySecs, for delays set before the schedule.
+            est.storage_reads += 1;
+        }
+        let delays = Self::claim_delay_schedule(&env, &token);
+        let bonus = Self::get_claim_bonus(env.clone(), token.clone());
+        let stream_secs = Self::get_stream_duration(env.clone(), token.clone());
+        let now = env.ledger().timestamp();
+
+        let mut total_payout: i128 = 0;
+        let mut bonus_due: i128 = 0;
+        let mut advanced: u32 = 0;
+        let mut cursor_open = true;
+        for k in 0..steps {
+            let i = walk.index(k);
+            // PeriodEntry, PeriodDepositTime.
+            est.storage_reads += 2;
+            let period_id: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PeriodEntry(token.clone(), i))
+                .unwrap();
+            let deposit_time: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PeriodDepositTime(token.clone(), period_id))
+                .unwrap_or(0);
+            if Self::in_claim_delay(&env, &delays, deposit_time) {
+                if !walk.newest {
+                    break;
+                }
+                walk.visit(i, false);
+                continue;
+            }
+            // PeriodPaused.
+            est.storage_reads += 1;
+            if Self::is_period_paused(env.clone(), token.clone(), period_id) {
+                if !walk.newest {
+                    break;
+                }
+                walk.visit(i, false);
+                continue;
+            }
+            // PushPaid, Swept, then Merkle root, ForPeriod snapshot and PeriodRevenue
+            // until one of them settles the period, plus PeriodSupply in balance mode.
+            let store = env.storage().persistent();
+            let swept = store.has(&EscrowKey::Swept(token.clone(), period_id));
+            let has_root = store.has(&MerkleKey::Root(token.clone(), period_id));
+            let has_snapshot = store.has(&SnapshotKey::ForPeriod(token.clone(), period_id));
+            est.storage_reads += match (swept, has_root, has_snapshot) {
+                (true, _, _) => 2,
+                (false, true, _) => 3,
+                (false, false, true) => 4,
+                (false, false, false) => 5 + balance.is_some() as u32,
+            };
+            let (payout, unlocked) = Self::streamed_payout(
+                &env,
+                &token,
+                period_id,
+                &holder,
+                share_bps,
+                balance,
+                now.saturating_sub(deposit_time),
+                stream_secs,
+            );
+            if payout > 0 {
+                // Overdraw check (PeriodRevenue, Distributed), then Distributed read and
+                // write.
+                est.storage_reads += 3;
+                est.storage_writes += 1;
+            }
+            cursor_open &= unlocked && !walk.newest;
+            walk.visit(i, unlocked);
+            if cursor_open {
+                advanced += 1;
+            } else if payout > 0 {
+                // PushPaid read and write for a period paid past the cursor.
+                est.storage_reads += 1;
+                est.storage_writes += 1;
+            }
+            bonus_due += Self::prompt_bonus(&bonus, payout, deposit_time, now);
+            total_payout += payout;
+            est.periods += 1;
+        }
+
+        let (cursor, band) = if walk.newest {
+            walk.finish()
+        } else {
+            (start_idx + advanced, None)
+        };
+        let settled_to = band.map(|(_, hi)| hi).unwrap_or(cursor);
+        if advanced > 0 || (walk.newest && walk.run.is_some()) || total_payout > 0 {
+            // LastClaimedIdx write (and the SettledBand write when newest first),
+            // ClaimWatermark read and write when it rises, HistoryCount read and write plus
+            // the History receipt, then EventVersioningEnabled.
+            est.storage_writes += 3 + walk.newest as u32;
+            est.storage_reads += 3;
+            let watermark: u32 = env
+                .storage()
+                .persistent()
+                .get(&ClaimKey::ClaimWatermark(token.clone()))
+                .unwrap_or(0);
+            if settled_to > watermark {
+                est.storage_writes += 1;
+            }
+        }
+        let mut bonus_paid: i128 = 0;
+        if bonus_due > 0 {
+            // BonusPool read, and write when it pays.
+            est.storage_reads += 1;
+            bonus_paid =
+                core::cmp::min(bonus_due, Self::get_bonus_pool(env.clone(), token.clone()));
+            if bonus_paid > 0 {
+                est.storage_writes += 1;
+            }
+        }
+        let amount = total_payout + bonus_paid;
+        if amount > 0 {
+            // PaymentToken, Donation, SubAccounts, and AutoReinvest once every period is
+            // settled; a donation adds a second transfer unless it takes all.
+            let settled = cursor == period_count && band.is_none();
+            est.storage_reads += 3 + settled as u32;
+            let donated = Self::get_donation(env.clone(), token.clone(), holder.clone())
+                .map(|d| math::bps_of(amount, d.bps))
+                .unwrap_or(0);
+            let mut reinvested: i128 = 0;
+            if settled && Self::is_auto_reinvest(env.clone(), token.clone(), holder.clone()) {
+                // ReinvestRate, Closed, OfferingIssuer, TotalShareBps and the allocation
+                // cap (TestnetMode, ByToken, OfferItem twice).
+                est.storage_reads += 8;
+                if let Some((_, spent, _)) =
+                    Self::reinvest_plan(&env, &token, &holder, amount - donated)
+                {
+                    // HolderShare read, then the share update's reads (HolderShare,
+                    // TotalShareBps, the allocation cap's five, the registry check) and
+                    // two writes.
+                    est.storage_reads += 9;
+                    est.storage_writes += 2;
+                    est.transfers += 1;
+                    reinvested = spent;
+                }
+            }
+            est.transfers += (donated > 0) as u32 + (amount > donated + reinvested) as u32;
+        }
+        est
+    }
+
+    /// Number of claim receipts recorded for `holder` on `token`.
+    pub fn get_claim_history_count(env: Env, token: Address, holder: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ClaimKey::HistoryCount(token, holder))
+            .unwrap_or(0)
+    }
+
+    /// Recompute receipt `seq` of `holder` on `token` from stored period revenue, pushes
+    /// and the share recorded in the receipt, and return whether each period's payout and
+    /// their sum match the amount paid (less any bonus). Sweeping a period after the claim
+    /// does not change the result; a zero payout for a swept period is taken as swept
+    /// before the claim. `None` if there is no such receipt. Periods only partly unlocked
+    /// by streaming at the time, or paid by a newest-first claim, do not recompute.
+    pub fn verify_claim(env: Env, token: Address, holder: Address, seq: u32) -> Option<bool> {
+        let record: ClaimRecord = env.storage().persistent().get(&ClaimKey::History(
+            token.clone(),
+            holder.clone(),
+            seq,
+        ))?;
+        if record.payouts.len() != record.periods.len() {
+            return Some(false);
+        }
+        let mut paid: i128 = 0;
+        for (period_id, payout) in record.periods.iter().zip(record.payouts.iter()) {
+            paid += payout;
+            let swept = env
+                .storage()
+                .persistent()
+                .has(&EscrowKey::Swept(token.clone(), period_id));
+            if swept && payout == 0 {
+                continue;
+            }
+            let pushed =
+                Self::get_pushed_amount(env.clone(), token.clone(), period_id, holder.clone());
+            let entitlement =
+                Self::period_entitlement(&env, &token, period_id, record.share_bps, record.balance);
+            if payout != (entitlement - pushed).max(0) {
+                return Some(false);
+            }
+        }
+        Some(paid == record.amount - record.bonus)
+    }
+
+    /// Return a page of `holder`'s claim receipts on `token`, oldest first. Limit capped
+    /// at the network page limit (20; 10 on Mainnet).
+    pub fn get_claim_history_page(
+        env: Env,
+        token: Address,
+        holder: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<ClaimRecord>, Option<u32>) {
+        let count = Self::get_claim_history_count(env.clone(), token.clone(), holder.clone());
+
+        let page_limit = Self::get_network_limits(env.clone()).page_limit;
+        let effective_limit = if limit == 0 || limit > page_limit {
+            page_limit
+        } else {
+            limit
+        };
+
+        if start >= count {
+            return (Vec::new(&env), None);
+        }
+
+        let end = core::cmp::min(start + effective_limit, count);
+        let mut results = Vec::new(&env);
+        for i in start..end {
+            let key = ClaimKey::History(token.clone(), holder.clone(), i);
+            let record: ClaimRecord = env.storage().persistent().get(&key).unwrap();
+            results.push_back(record);
+        }
+
+        let next_cursor = if end < count { Some(end) } else { None };
+        (results, next_cursor)
+    }
+
+    /// Return unclaimed period IDs for a holder on an offering.
+    pub fn get_pending_periods(env: Env, token: Address, holder: Address) -> Vec<u64> {
+        let count_key = DataKey::PeriodCount(token.clone());
+        let period_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        let idx_key = DataKey::LastClaimedIdx(token.clone(), holder);
+        let start_idx: u32 = env.storage().persistent().get(&idx_key).unwrap_or(0);
+
+        let mut periods = Vec::new(&env);
+        for i in start_idx..period_count {
+            let entry_key = DataKey::PeriodEntry(token.clone(), i);
+            let period_id: u64 = env.storage().persistent().get(&entry_key).unwrap();
+            periods.push_back(period_id);
+        }
+        periods
+    }
+
+    /// Preview the total claimable amount for a holder without claiming.
+    /// Respects per-offering claim delay (#27): only sums periods past the delay.
+    pub fn get_claimable(env: Env, token: Address, holder: Address) -> i128 {
+        Self::pending_payout(&env, &token, &holder, 0)
+    }
+
+    /// What `holder` can claim from `token`, per payment token. An offering's deposits all
+    /// use its first payment token (`PaymentTokenMismatch` otherwise), so this holds at most
+    /// one `(payment_token, amount)` entry; it is empty before the first deposit or when
+    /// nothing is claimable.
+    pub fn get_claimable_by_asset(
+        env: Env,
+        token: Address,
+        holder: Address,
+    ) -> Vec<(Address, i128)> {
+        let mut by_asset = Vec::new(&env);
+        let Some(payment_token) = env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&DataKey::PaymentToken(token.clone()))
+        else {
+            return by_asset;
+        };
+        let amount = Self::get_claimable(env, token, holder);
+        if amount > 0 {
+            by_asset.push_back((payment_token, amount));
+        }
+        by_asset
+    }
+
+    /// `holder`'s claimable entitlement on `token` as a `ClaimVoucher` for companion
+    /// contracts. Consumers should read it from this contract directly (cross-contract
+    /// call) or compare a relayed voucher's `digest` with a fresh one; the digest leaves out
+    /// `ledger`, so it matches at any later ledger until a claim or deposit changes it.
+    pub fn get_claim_voucher(env: Env, token: Address, holder: Address) -> ClaimVoucher {
+        let contract = env.current_contract_address();
+        let claimable = Self::get_claimable(env.clone(), token.clone(), holder.clone());
+        let nonce = Self::get_claim_history_count(env.clone(), token.clone(), holder.clone());
+        let ledger = env.ledger().sequence();
+        let digest = env
+            .crypto()
+            .sha256(
+                &(
+                    contract.clone(),
+                    holder.clone(),
+                    token.clone(),
+                    claimable,
+                    nonce,
+                )
+                    .to_xdr(&env),
+            )
+            .to_bytes();
+        ClaimVoucher {
+            contract,
+            payment_token: env
+                .storage()
+                .persistent()
+                .get(&DataKey::PaymentToken(token.clone())),
+            holder,
+            token,
+            claimable,
+            nonce,
+            ledger,
+            digest,
+        }
+    }
+
+    /// Per-period view of what `holder` is owed: `(period_id, amount, claimable_at)` for the
+    /// first `claim_periods` (see `get_network_limits`) unclaimed periods, in claim order, skipping periods that
+    /// pay nothing. `claimable_at` is when the period's claim delay ends (a past time means
+    /// ready now), or `u64::MAX` while the period is paused. `amount` is the full remaining
+    /// entitlement; under claim streaming it unlocks gradually after the deposit.
+    pub fn get_claimable_breakdown(
+        env: Env,
+        token: Address,
+        holder: Address,
+    ) -> Vec<(u64, i128, u64)> {
+        let mut breakdown = Vec::new(&env);
+        let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+        let balance = Self::live_balance(&env, &token, &holder);
+        if share_bps == 0 && balance.unwrap_or(0) == 0 {
+            return breakdown;
+        }
+
+        let store = env.storage().persistent();
+        let period_count: u32 = store.get(&DataKey::PeriodCount(token.clone())).unwrap_or(0);
+        let start_idx: u32 = store
+            .get(&DataKey::LastClaimedIdx(token.clone(), holder.clone()))
+            .unwrap_or(0);
+        let claim_cap = Self::get_network_limits(env.clone()).claim_periods;
+        let end = core::cmp::min(period_count, start_idx.saturating_add(claim_cap));
+        let delays = Self::claim_delay_schedule(&env, &token);
+        for i in start_idx..end {
+            let period_id: u64 = store.get(&DataKey::PeriodEntry(token.clone(), i)).unwrap();
+            let amount = Self::share_payout(&env, &token, period_id, &holder, share_bps, balance);
+            if amount == 0 {
+                continue;
+            }
+            let claimable_at = if Self::is_period_paused(env.clone(), token.clone(), period_id) {
+                u64::MAX
+            } else {
+                let deposit_time: u64 = store
+                    .get(&DataKey::PeriodDepositTime(token.clone(), period_id))
+                    .unwrap_or(0);
+                deposit_time.saturating_add(Self::delay_at(&delays, deposit_time))
+            };
+            breakdown.push_back((period_id, amount, claimable_at));
+        }
+        breakdown
+    }
+
+    // ── Escrow accounting and residual sweep ───────────────────
+
+    /// Deposit, fee, payouts and what remains held for a period, so issuers can reconcile
+    /// escrow balances against liabilities. `None` if the period was never deposited.
+    pub fn get_period_accounting(
+        env: Env,
+        token: Address,
+        period_id: u64,
+    ) -> Option<PeriodAccounting> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::PeriodRevenue(token.clone(), period_id))
+        {
+            return None;
+        }
+        let escrow = Self::get_period_escrow(env.clone(), token.clone(), period_id);
+        let fee: i128 = env
+            .storage()
+            .persistent()
+            .get(&EscrowKey::Fee(token.clone(), period_id))
+            .unwrap_or(0);
+        Some(PeriodAccounting {
+            deposited: escrow.deposited + fee,
+            fee,
+            claimed: escrow.distributed,
+            swept: escrow.swept,
+            residual: escrow.deposited - escrow.distributed - escrow.swept,
+            reported: env
+                .storage()
+                .persistent()
+                .get(&EscrowKey::Reported(token, period_id)),
+        })
+    }
+
+    /// Deposit metadata for a period in one call, or `None` if it was never deposited.
+    pub fn get_period_info(env: Env, token: Address, period_id: u64) -> Option<PeriodInfo> {
+        let amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodRevenue(token.clone(), period_id))?;
+        let escrow = Self::get_period_escrow(env.clone(), token.clone(), period_id);
+        Some(PeriodInfo {
+            amount,
+            deposit_time: env
+                .storage()
+                .persistent()
+                .get(&DataKey::PeriodDepositTime(token.clone(), period_id))
+                .unwrap_or(0),
+            snapshot_ref: Self::get_distribution_root(env, token, period_id).map(Bytes::from),
+            finalized: escrow.closed,
+            claimed_total: escrow.distributed,
+        })
+    }
+
+    /// Deposited, distributed and swept amounts for a period.
+    pub fn get_period_escrow(env: Env, token: Address, period_id: u64) -> PeriodEscrow {
+        let swept: Option<i128> = env
+            .storage()
+            .persistent()
+            .get(&EscrowKey::Swept(token.clone(), period_id));
+        PeriodEscrow {
+            deposited: env
+                .storage()
+                .persistent()
+                .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+                .unwrap_or(0),
+            distributed: env
+                .storage()
+                .persistent()
+                .get(&EscrowKey::Distributed(token, period_id))
+                .unwrap_or(0),
+            swept: swept.unwrap_or(0),
+            closed: swept.is_some(),
+        }
+    }
+
+    /// Claim window in seconds for an offering (0 = no expiry).
+    pub fn get_claim_expiry(env: Env, token: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&EscrowKey::ClaimExpiry(token))
+            .unwrap_or(0)
+    }
+
+    /// Treasury that receives reclaimed revenue, if one is set.
+    pub fn get_reclaim_treasury(env: Env, token: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&EscrowKey::ReclaimTreasury(token))
+    }
+
+    /// Amount already pushed to `holder` for `period_id` by `distribute_batch`.
+    pub fn get_pushed_amount(env: Env, token: Address, period_id: u64, holder: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PushPaid(token, period_id, holder))
+            .unwrap_or(0)
+    }
+
+    /// Period whose distribution root `period_id` reuses, if `reuse_snapshot` was recorded.
+    pub fn get_snapshot_source(env: Env, token: Address, period_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&MerkleKey::ReusedFrom(token, period_id))
+    }
+
+    /// Return the distribution root for a period, if any.
+    pub fn get_distribution_root(env: Env, token: Address, period_id: u64) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&MerkleKey::Root(token, period_id))
+    }
+
+    /// Total supply used for balance-derived shares, if the mode is on.
+    pub fn get_balance_mode(env: Env, token: Address) -> Option<i128> {
+        env.storage()
+            .persistent()
+            .get(&SnapshotKey::LiveSupply(token))
+    }
+
+    /// Snapshot that `period_id` pays against, if `set_period_snapshot` was called.
+    pub fn get_period_snapshot(env: Env, token: Address, period_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&SnapshotKey::ForPeriod(token, period_id))
+    }
+
+    /// Balance recorded for `holder` in snapshot `snapshot_ref` (0 if none).
+    pub fn get_snapshot_balance(
+        env: Env,
+        token: Address,
+        snapshot_ref: u64,
+        holder: Address,
+    ) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&SnapshotKey::Balance(token, snapshot_ref, holder))
+            .unwrap_or(0)
+    }
+
+    /// Return true if `holder` has already claimed `period_id` with a proof.
+    pub fn is_proof_claimed(env: Env, token: Address, period_id: u64, holder: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&MerkleKey::Claimed(token, period_id, holder))
+    }
+
+    /// Return true if claims for `period_id` are paused.
+    pub fn is_period_paused(env: Env, token: Address, period_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PeriodPaused(token, period_id))
+            .unwrap_or(false)
+    }
+
+    /// Get per-offering claim delay in seconds. 0 = immediate claim.
+    pub fn get_claim_delay(env: Env, token: Address) -> u64 {
+        let key = DataKey::ClaimDelaySecs(token);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Claim delay in seconds that applies to `period_id` of `token` (0 if the period does
+    /// not exist or has no delay).
+    pub fn get_period_claim_delay(env: Env, token: Address, period_id: u64) -> u64 {
+        let deposit_time: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodDepositTime(token.clone(), period_id));
+        match deposit_time {
+            Some(t) => Self::delay_at(&Self::claim_delay_schedule(&env, &token), t),
+            None => 0,
+        }
+    }
+
+    /// Prompt-claim bonus for an offering, if enabled.
+    pub fn get_claim_bonus(env: Env, token: Address) -> Option<ClaimBonus> {
+        env.storage().persistent().get(&ClaimKey::Bonus(token))
+    }
+
+    /// Remaining bonus pool balance for an offering.
+    pub fn get_bonus_pool(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ClaimKey::BonusPool(token))
+            .unwrap_or(0)
+    }
+
+    /// Return the total number of deposited periods for an offering token.
+    pub fn get_period_count(env: Env, token: Address) -> u32 {
+        let count_key = DataKey::PeriodCount(token);
+        env.storage().persistent().get(&count_key).unwrap_or(0)
+    }
+
+    // ── State export ──────────────────────────────────────────────
+
+    /// Page of all offerings by offering ID, for mirroring contract state. `start` is an
+    /// offering ID (IDs start at 1 and are never reused, so cursors stay valid while new
+    /// offerings register); `limit` capped at the network page limit. IDs whose token no
+    /// longer resolves to an offering are skipped but count toward the page.
+    pub fn export_offerings_page(
+        env: Env,
+        start: u64,
+        limit: u32,
+    ) -> (Vec<ExportedOffering>, Option<u64>) {
+        let last_id = Self::get_total_offering_count(env.clone());
+        let limit = Self::export_limit(&env, limit);
+        let mut results = Vec::new(&env);
+        let start = start.max(1);
+        if start > last_id {
+            return (results, None);
+        }
+
+        let end = core::cmp::min(start.saturating_add(limit as u64), last_id + 1);
+        for id in start..end {
+            let Some(offering) = Self::get_offering_by_id(env.clone(), id) else {
+                continue;
+            };
+            let token = offering.token.clone();
+            results.push_back(ExportedOffering {
+                id,
+                offering,
+                period_count: Self::get_period_count(env.clone(), token.clone()),
+                holder_count: Self::get_holder_count(env.clone(), token),
+            });
+        }
+
+        let next_cursor = if end <= last_id { Some(end) } else { None };
+        (results, next_cursor)
+    }
+
+    /// Page of `token`'s holder index in first-assignment order, with claim progress.
+    /// Same cursor as `get_holders_page`.
+    pub fn export_holders_page(
+        env: Env,
+        token: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<ExportedHolder>, Option<u32>) {
+        let count = Self::get_holder_count(env.clone(), token.clone());
+        let limit = Self::export_limit(&env, limit);
+        let mut results = Vec::new(&env);
+        if start >= count {
+            return (results, None);
+        }
+
+        let store = env.storage().persistent();
+        let end = core::cmp::min(start.saturating_add(limit), count);
+        for i in start..end {
+            let holder: Address = store.get(&DataKey::HolderAt(token.clone(), i)).unwrap();
+            results.push_back(ExportedHolder {
+                share_bps: Self::get_holder_share(env.clone(), token.clone(), holder.clone()),
+                next_claim_idx: store
+                    .get(&DataKey::LastClaimedIdx(token.clone(), holder.clone()))
+                    .unwrap_or(0),
+                holder,
+            });
+        }
+
+        let next_cursor = if end < count { Some(end) } else { None };
+        (results, next_cursor)
+    }
+
+    /// Page of `token`'s deposited periods in period-list order (the order claims walk).
+    /// `start` is a list index. Deposits append, except that a late deposit of an older
+    /// period shifts the entries after it by one (see `insert_period_entry`); entries a claim
+    /// has passed never move.
+    pub fn export_periods_page(
+        env: Env,
+        token: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<ExportedPeriod>, Option<u32>) {
+        let count = Self::get_period_count(env.clone(), token.clone());
+        let limit = Self::export_limit(&env, limit);
+        let mut results = Vec::new(&env);
+        if start >= count {
+            return (results, None);
+        }
+
+        let store = env.storage().persistent();
+        let end = core::cmp::min(start.saturating_add(limit), count);
+        for i in start..end {
+            let period_id: u64 = store.get(&DataKey::PeriodEntry(token.clone(), i)).unwrap();
+            results.push_back(ExportedPeriod {
+                period_id,
+                revenue: store
+                    .get(&DataKey::PeriodRevenue(token.clone(), period_id))
+                    .unwrap_or(0),
+                deposited_at: store
+                    .get(&DataKey::PeriodDepositTime(token.clone(), period_id))
+                    .unwrap_or(0),
+            });
+        }
+
+        let next_cursor = if end < count { Some(end) } else { None };
+        (results, next_cursor)
+    }
+
+    // ── On-chain distribution simulation (#29) ────────────────────
+
+    /// Read-only: simulate distribution for sample inputs without mutating state.
+    /// Returns expected payouts per holder and total. Uses offering's rounding mode.
+    /// For integrators to preview outcomes before executing deposit/claim flows.
+    pub fn simulate_distribution(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        amount: i128,
+        holder_shares: Vec<(Address, u32)>,
+    ) -> SimulateDistributionResult {
+        let mode = Self::get_rounding_mode(env.clone(), issuer, token.clone());
+        let mut total: i128 = 0;
+        let mut payouts = Vec::new(&env);
+        for i in 0..holder_shares.len() {
+            let (holder, share_bps) = holder_shares.get(i).unwrap();
+            let payout = if share_bps > 10_000 {
+                0_i128
+            } else {
+                Self::compute_share(env.clone(), amount, share_bps, mode)
+            };
+            total = total.saturating_add(payout);
+            payouts.push_back((holder.clone(), payout));
+        }
+        SimulateDistributionResult {
+            total_distributed: total,
+            payouts,
+        }
+    }
+
+    /// Get the capital raise for an offering, if one was opened.
+    pub fn get_raise(env: Env, token: Address) -> Option<CapitalRaise> {
+        env.storage().persistent().get(&DataKey::Raise(token))
+    }
+
+    /// Unsettled subscription amount for `investor` (0 if none or already settled).
+    pub fn get_subscription(env: Env, token: Address, investor: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RaiseContribution(token, investor))
+            .unwrap_or(0)
+    }
+
+    /// Get the secondary transfer policy for an offering (non-transferable by default).
+    pub fn get_share_transfer_config(env: Env, token: Address) -> ShareTransferConfig {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ShareTransferConfig(token))
+            .unwrap_or_default()
+    }
+
+    /// Get a share transfer record by id.
+    pub fn get_share_transfer(env: Env, token: Address, transfer_id: u32) -> Option<ShareTransfer> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ShareTransfer(token, transfer_id))
+    }
+
+    /// Get a share listing by id.
+    pub fn get_listing(env: Env, token: Address, listing_id: u32) -> Option<ShareListing> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ShareListing(token, listing_id))
+    }
+
+    /// Share bps `seller` has committed to active listings and pending share transfers.
+    /// Only the rest of its share can be listed or proposed.
+    pub fn get_committed_share(env: Env, token: Address, seller: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&EscrowKey::CommittedBps(token, seller))
+            .unwrap_or(0)
+    }
+
+    /// Read-only: preview distributing `amount` across the on-chain holder registry.
+    /// Uses each holder's current share and the same truncating formula as `claim`
+    /// (`amount * share_bps / 10000`). Blacklisted and zero-share holders are skipped.
+    /// Paginates over the registry like `get_offerings_page`; `limit` capped at the network page limit.
+    pub fn simulate_distribution_live(
+        env: Env,
+        token: Address,
+        amount: i128,
+        start: u32,
+        limit: u32,
+    ) -> (SimulateDistributionResult, Option<u32>) {
+        let count = Self::get_holder_count(env.clone(), token.clone());
+        let page_limit = Self::get_network_limits(env.clone()).page_limit;
+        let effective_limit = if limit == 0 || limit > page_limit {
+            page_limit
+        } else {
+            limit
+        };
+
+        let mut total: i128 = 0;
+        let mut payouts = Vec::new(&env);
+        let end = core::cmp::min(start.saturating_add(effective_limit), count);
+        for i in start..end {
+            let holder: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::HolderAt(token.clone(), i))
+                .unwrap();
+            if Self::is_blacklisted(env.clone(), token.clone(), holder.clone()) {
+                continue;
+            }
+            let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+            if share_bps == 0 {
+                continue;
+            }
+            let payout = amount.saturating_mul(share_bps as i128) / 10_000;
+            total = total.saturating_add(payout);
+            payouts.push_back((holder, payout));
+        }
+
+        let next_cursor = if end < count { Some(end) } else { None };
+        (
+            SimulateDistributionResult {
+                total_distributed: total,
+                payouts,
+            },
+            next_cursor,
+        )
+    }
+
+    /// Get the admin address, if set.
+    pub fn get_admin(env: Env) -> Option<Address> {
+        Self::read_admin(&env)
+    }
+
+    /// Dry run of `freeze`: return the admin whose signature it requires. Fails like
+    /// `freeze` when no admin is set.
+    pub fn preview_freeze(env: Env) -> Result<Address, RevoraError> {
+        Self::read_admin(&env).ok_or(RevoraError::LimitReached)
+    }
+
+    /// Return true if the contract is frozen.
+    pub fn is_frozen(env: Env) -> bool {
+        Self::read_flag(&env, DataKey::Frozen)
+    }
+
+    /// Get misbehavior counters and suspension state for `issuer`.
+    pub fn get_issuer_reputation(env: Env, issuer: Address) -> IssuerReputation {
+        env.storage()
+            .persistent()
+            .get(&DataKey::IssuerReputation(issuer))
+            .unwrap_or_default()
+    }
+
+    // ── Storage usage metrics ──────────────────────────────────
+
+    /// Approximate persistent entry counts per subsystem. All zero on a fresh contract.
+    pub fn get_storage_metrics(env: Env) -> StorageMetrics {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StorageMetrics)
+            .unwrap_or_default()
+    }
+
+    /// Ledger timestamp from which the pending issuer transfer can no longer be accepted.
+    /// `None` if nothing is pending or proposals do not expire.
+    pub fn get_issuer_transfer_expiry(env: Env, token: Address) -> Option<u64> {
+        let ttl = Self::get_config(env.clone()).transfer_ttl_secs;
+        if ttl == 0 {
+            return None;
+        }
+        let proposed_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&OfferingKey::TransferProposedAt(token))?;
+        Some(proposed_at.saturating_add(ttl))
+    }
+
+    /// Get the pending issuer transfer for an offering, if any.
+    pub fn get_pending_issuer_transfer(env: Env, token: Address) -> Option<Address> {
+        let pending_key = DataKey::PendingIssuerTransfer(token);
+        env.storage().persistent().get(&pending_key)
+    }
+
+    /// Issuer transfers pending to `address`, as `(token, current_issuer)` in proposal
+    /// order (at most MAX_TRANSFER_INBOX). Lets a proposed issuer find offerings to accept.
+    pub fn get_incoming_transfers(env: Env, address: Address) -> Vec<(Address, Address)> {
+        let inbox: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&OfferingKey::TransferInbox(address))
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut out = Vec::new(&env);
+        for token in inbox.iter() {
+            if let Some(issuer) = Self::get_current_issuer(&env, &token) {
+                out.push_back((token, issuer));
+            }
+        }
+        out
+    }
+
+    /// Number of issuer transfers pending across all offerings.
+    pub fn get_pending_transfer_count(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&OfferingKey::PendingTransferCount)
+            .unwrap_or(0)
+    }
+
+    /// Page through every pending issuer transfer as `(token, current_issuer, new_issuer)`,
+    /// for operators monitoring stale proposals. `limit` capped at the network page limit;
+    /// returns the next cursor like `get_offerings_page`. Accepting or cancelling moves the
+    /// last entry into the freed slot, so order is not stable across those calls.
+    pub fn get_all_pending_transfers_page(
+        env: Env,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<(Address, Address, Address)>, Option<u32>) {
+        let count = Self::get_pending_transfer_count(env.clone());
+
+        let page_limit = Self::get_network_limits(env.clone()).page_limit;
+        let effective_limit = if limit == 0 || limit > page_limit {
+            page_limit
+        } else {
+            limit
+        };
+
+        if start >= count {
+            return (Vec::new(&env), None);
+        }
+
+        let end = core::cmp::min(start + effective_limit, count);
+        let mut results = Vec::new(&env);
+
+        for i in start..end {
+            let token: Address = env
+                .storage()
+                .persistent()
+                .get(&OfferingKey::PendingTransferAt(i))
+                .unwrap();
+            let new_issuer: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PendingIssuerTransfer(token.clone()))
+                .unwrap();
+            let issuer = Self::get_current_issuer(&env, &token).unwrap();
+            results.push_back((token, issuer, new_issuer));
+        }
+
+        let next_cursor = if end < count { Some(end) } else { None };
+        (results, next_cursor)
+    }
+
+    /// Calculate the total distributable revenue for an offering.
+    ///
+    /// This is a helper function for off-chain verification.
+    /// Returns `OfferingNotFound` if the offering does not exist.
+    pub fn calculate_total_distributable(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        total_revenue: i128,
+    ) -> Result<i128, RevoraError> {
+        let offering =
+            Self::get_offering(env, issuer, token).ok_or(RevoraError::OfferingNotFound)?;
+
+        if total_revenue == 0 {
+            return Ok(0);
+        }
+
+        Ok(math::bps_of(total_revenue, offering.revenue_share_bps))
+    }
+
+    /// Return true if testnet mode is enabled.
+    pub fn is_testnet_mode(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get::<DataKey, bool>(&DataKey::TestnetMode)
+            .unwrap_or(false)
+    }
+}
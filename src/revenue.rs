@@ -0,0 +1,1370 @@
+//! Revenue-side entrypoints: reports and attestations, deposits, concentration limits,
+//! pushed and Merkle distributions, snapshots, period pauses, sweeps and bonus pools.
+
+use super::*;
+
+#[contractimpl]
+impl RevoraRevenueShare {
+    /// Record a revenue report for an offering. Updates audit summary (#34).
+    /// Fails with `ConcentrationLimitExceeded` (#26) if concentration enforcement is on and current concentration exceeds limit.
+    /// In testnet mode, concentration enforcement is skipped.
+    /// `override_existing`: if true, allows overwriting a previously reported period.
+    /// Overrides are compare-and-swap: `expected_previous` must equal the currently stored
+    /// amount (`None` if the period has not been reported), otherwise the call fails with
+    /// `StaleOverride` and nothing changes. Ignored when `override_existing` is false.
+    /// Issuer authorization is bound to
+    /// `(token, payout_asset, amount, period_id, override_existing, expected_previous)`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn report_revenue(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        payout_asset: Address,
+        amount: i128,
+        period_id: u64,
+        override_existing: bool,
+        expected_previous: Option<i128>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        // Verify offering exists and issuer is current
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+
+        Self::require_offering_not_paused(&env, &token)?;
+        Self::require_offering_open(&env, &token)?;
+        if Self::get_issuer_reputation(env.clone(), issuer.clone()).suspended {
+            return Err(RevoraError::ReportingSuspended);
+        }
+        // Bind the signature to the exact figures being reported.
+        issuer.require_auth_for_args(
+            (
+                token.clone(),
+                payout_asset.clone(),
+                amount,
+                period_id,
+                override_existing,
+                expected_previous,
+            )
+                .into_val(&env),
+        );
+        Self::require_on_schedule(&env, &token, period_id)?;
+
+        let offering = Self::get_offering(env.clone(), issuer.clone(), token.clone())
+            .ok_or(RevoraError::OfferingNotFound)?;
+        if offering.payout_asset != payout_asset {
+            return Err(RevoraError::PayoutAssetMismatch);
+        }
+
+        // Skip concentration enforcement in testnet mode
+        let testnet_mode = Self::is_testnet_mode(env.clone());
+        if !testnet_mode {
+            // Holder concentration guardrail (#26): reject if enforce and over limit
+            let limit_key = DataKey::ConcentrationLimit(issuer.clone(), token.clone());
+            if let Some(config) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, ConcentrationLimitConfig>(&limit_key)
+            {
+                if config.enforce && config.max_bps > 0 {
+                    let curr_key = DataKey::CurrentConcentration(issuer.clone(), token.clone());
+                    let current: u32 = env.storage().persistent().get(&curr_key).unwrap_or(0);
+                    if current > config.max_bps {
+                        return Err(RevoraError::ConcentrationLimitExceeded);
+                    }
+                }
+            }
+        }
+
+        let blacklist = Self::get_blacklist(env.clone(), token.clone());
+
+        let key = DataKey::RevenueReports(issuer.clone(), token.clone());
+        let mut reports: Map<u64, (i128, u64)> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(&env));
+        let current_timestamp = env.ledger().timestamp();
+
+        if override_existing && reports.get(period_id).map(|(a, _)| a) != expected_previous {
+            return Err(RevoraError::StaleOverride);
+        }
+
+        let (previous_amount, accepted) = match reports.get(period_id) {
+            Some((existing_amount, _timestamp)) => {
+                if override_existing {
+                    reports.set(period_id, (amount, current_timestamp));
+                    env.storage().persistent().set(&key, &reports);
+
+                    env.events().publish(
+                        (EVENT_REVENUE_REPORT_OVERRIDE, issuer.clone(), token.clone()),
+                        (amount, period_id, existing_amount, blacklist.clone()),
+                    );
+
+                    env.events().publish(
+                        (
+                            EVENT_REVENUE_REPORT_OVERRIDE_ASSET,
+                            issuer.clone(),
+                            token.clone(),
+                            payout_asset.clone(),
+                        ),
+                        (amount, period_id, existing_amount, blacklist.clone()),
+                    );
+                    (Some(existing_amount), true)
+                } else {
+                    // Rejection events below already record this strike.
+                    Self::add_strike(&env, &issuer, StrikeReason::RejectedOverride);
+                    env.events().publish(
+                        (EVENT_REVENUE_REPORT_REJECTED, issuer.clone(), token.clone()),
+                        (amount, period_id, existing_amount, blacklist.clone()),
+                    );
+
+                    env.events().publish(
+                        (
+                            EVENT_REVENUE_REPORT_REJECTED_ASSET,
+                            issuer.clone(),
+                            token.clone(),
+                            payout_asset.clone(),
+                        ),
+                        (amount, period_id, existing_amount, blacklist.clone()),
+                    );
+                    (Some(existing_amount), false)
+                }
+            }
+            None => {
+                reports.set(period_id, (amount, current_timestamp));
+                env.storage().persistent().set(&key, &reports);
+                Self::update_metrics(&env, |m| m.reports = m.reports.saturating_add(1));
+
+                env.events().publish(
+                    (EVENT_REVENUE_REPORT_INITIAL, issuer.clone(), token.clone()),
+                    (amount, period_id, blacklist.clone()),
+                );
+
+                env.events().publish(
+                    (
+                        EVENT_REVENUE_REPORT_INITIAL_ASSET,
+                        issuer.clone(),
+                        token.clone(),
+                        payout_asset.clone(),
+                    ),
+                    (amount, period_id, blacklist.clone()),
+                );
+                (None, true)
+            }
+        };
+
+        // Backward-compatible event (preserve `blacklist` for additional publishes)
+        env.events().publish(
+            (EVENT_REVENUE_REPORTED, issuer.clone(), token.clone()),
+            (amount, period_id, blacklist.clone()),
+        );
+
+        env.events().publish(
+            (
+                EVENT_REVENUE_REPORTED_ASSET,
+                issuer.clone(),
+                token.clone(),
+                payout_asset.clone(),
+            ),
+            (amount, period_id),
+        );
+
+        // Optionally emit versioned v1 events for forward-compatible consumers
+        if Self::is_event_versioning_enabled(env.clone()) {
+            env.events().publish(
+                (EVENT_REV_INIT_V1, issuer.clone(), token.clone()),
+                (EVENT_SCHEMA_VERSION, amount, period_id, blacklist.clone()),
+            );
+
+            env.events().publish(
+                (
+                    EVENT_REV_INIA_V1,
+                    issuer.clone(),
+                    token.clone(),
+                    payout_asset.clone(),
+                ),
+                (EVENT_SCHEMA_VERSION, amount, period_id, blacklist.clone()),
+            );
+
+            env.events().publish(
+                (EVENT_REV_REP_V1, issuer.clone(), token.clone()),
+                (EVENT_SCHEMA_VERSION, amount, period_id, blacklist.clone()),
+            );
+
+            env.events().publish(
+                (
+                    EVENT_REV_REPA_V1,
+                    issuer.clone(),
+                    token.clone(),
+                    payout_asset.clone(),
+                ),
+                (EVENT_SCHEMA_VERSION, amount, period_id),
+            );
+
+            env.events().publish(
+                (EVENT_REV_REP_V2, issuer.clone(), token.clone()),
+                RevenueReportedV2 {
+                    payout_asset: payout_asset.clone(),
+                    period_id,
+                    amount,
+                    previous_amount,
+                    accepted,
+                    blacklist: blacklist.clone(),
+                    currency: Self::get_accounting_currency(env.clone(), token.clone()),
+                },
+            );
+        }
+
+        // Audit log summary (#34): maintain per-offering total revenue and report count
+        let summary_key = DataKey::AuditSummary(issuer.clone(), token.clone());
+        let mut summary: AuditSummary =
+            env.storage()
+                .persistent()
+                .get(&summary_key)
+                .unwrap_or(AuditSummary {
+                    total_revenue: 0,
+                    report_count: 0,
+                });
+        summary.total_revenue = summary.total_revenue.saturating_add(amount);
+        summary.report_count = summary.report_count.saturating_add(1);
+        env.storage().persistent().set(&summary_key, &summary);
+
+        Ok(())
+    }
+
+    // ── Holder concentration guardrail (#26) ───────────────────
+
+    /// Set per-offering concentration limit. Caller must be the offering issuer.
+    /// `max_bps`: max allowed single-holder share in basis points (0 = disable).
+    /// `enforce`: if true, report_revenue will fail when reported concentration exceeds max_bps.
+    pub fn set_concentration_limit(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        max_bps: u32,
+        enforce: bool,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        // Verify offering exists and issuer is current
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::LimitReached)?;
+
+        if current_issuer != issuer {
+            return Err(RevoraError::LimitReached);
+        }
+
+        issuer.require_auth();
+        let key = DataKey::ConcentrationLimit(issuer, token);
+        env.storage()
+            .persistent()
+            .set(&key, &ConcentrationLimitConfig { max_bps, enforce });
+        Ok(())
+    }
+
+    /// Report current top-holder concentration in bps. Emits warning event if over configured limit.
+    pub fn report_concentration(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        concentration_bps: u32,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        // Verify offering exists and issuer is current
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+
+        issuer.require_auth();
+        Self::store_concentration(&env, issuer, token, concentration_bps);
+        Ok(())
+    }
+
+    /// Compute top-holder concentration from offering token balances instead of trusting an
+    /// issuer-supplied figure. Issuer or admin. Reads the balance of every address in the
+    /// offering's holder registry; the denominator is their summed balance, so no caller
+    /// can leave holders out. `LimitReached` above MAX_CONCENTRATION_HOLDERS registered
+    /// holders. Stores the result and emits the same warning as `report_concentration`.
+    /// Returns the computed bps.
+    pub fn compute_concentration(
+        env: Env,
+        caller: Address,
+        token: Address,
+    ) -> Result<u32, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let issuer = Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        caller.require_auth();
+        if caller != issuer {
+            Self::require_admin(&env, &caller)?;
+        }
+        let holder_count = Self::get_holder_count(env.clone(), token.clone());
+        if holder_count > MAX_CONCENTRATION_HOLDERS {
+            return Err(RevoraError::LimitReached);
+        }
+
+        let token_client = token::Client::new(&env, &token);
+        let mut total: i128 = 0;
+        let mut top: i128 = 0;
+        for i in 0..holder_count {
+            let holder: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::HolderAt(token.clone(), i))
+                .ok_or(RevoraError::InvalidState)?;
+            let bal = token_client.balance(&holder);
+            total = total.checked_add(bal).ok_or(RevoraError::InvalidAmount)?;
+            if bal > top {
+                top = bal;
+            }
+        }
+        if total <= 0 {
+            return Err(RevoraError::ZeroTotalSupply);
+        }
+
+        let concentration_bps = (top * 10_000 / total) as u32;
+        Self::store_concentration(&env, issuer, token, concentration_bps);
+        Ok(concentration_bps)
+    }
+
+    // ── Report attestations ────────────────────────────────────
+
+    /// Approve `auditor` to attest `token`'s revenue reports (issuer only). Idempotent;
+    /// at most MAX_AUDITORS (`LimitReached`).
+    pub fn add_auditor(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        auditor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let key = AuditKey::Auditors(token.clone());
+        let mut auditors = Self::get_auditors(env.clone(), token.clone());
+        if auditors.contains(&auditor) {
+            return Ok(());
+        }
+        if auditors.len() >= MAX_AUDITORS {
+            return Err(RevoraError::LimitReached);
+        }
+        auditors.push_back(auditor.clone());
+        env.storage().persistent().set(&key, &auditors);
+        env.events()
+            .publish((EVENT_AUDITOR_ADDED, issuer, token), auditor);
+        Ok(())
+    }
+
+    /// Revoke `auditor` (issuer only). Its existing attestations are kept.
+    pub fn remove_auditor(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        auditor: Address,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let mut auditors = Self::get_auditors(env.clone(), token.clone());
+        let Some(pos) = auditors.first_index_of(&auditor) else {
+            return Ok(());
+        };
+        auditors.remove(pos);
+        let key = AuditKey::Auditors(token.clone());
+        if auditors.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &auditors);
+        }
+        env.events()
+            .publish((EVENT_AUDITOR_REMOVED, issuer, token), auditor);
+        Ok(())
+    }
+
+    /// Record `auditor`'s sign-off on the revenue report for `period_id`, identified by the
+    /// hash of the reviewed document. `NotAuthorized` unless `auditor` is approved for
+    /// `token`; `InvalidState` if the period has no report. Attesting again replaces the
+    /// auditor's earlier attestation, e.g. after the issuer overrides the report.
+    pub fn attest_report(
+        env: Env,
+        auditor: Address,
+        token: Address,
+        period_id: u64,
+        report_hash: BytesN<32>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        auditor.require_auth();
+        let issuer = Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if !Self::get_auditors(env.clone(), token.clone()).contains(&auditor) {
+            return Err(RevoraError::NotAuthorized);
+        }
+        let (amount, _) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Map<u64, (i128, u64)>>(&DataKey::RevenueReports(
+                issuer.clone(),
+                token.clone(),
+            ))
+            .and_then(|reports| reports.get(period_id))
+            .ok_or(RevoraError::InvalidState)?;
+
+        let attestation = Attestation {
+            auditor: auditor.clone(),
+            report_hash: report_hash.clone(),
+            amount,
+            attested_at: env.ledger().timestamp(),
+        };
+        let mut attestations = Self::get_attestations(env.clone(), token.clone(), period_id);
+        match attestations.iter().position(|a| a.auditor == auditor) {
+            Some(pos) => attestations.set(pos as u32, attestation),
+            None => attestations.push_back(attestation),
+        }
+        env.storage().persistent().set(
+            &AuditKey::Attestations(token.clone(), period_id),
+            &attestations,
+        );
+        env.events().publish(
+            (EVENT_REPORT_ATTESTED, auditor, token),
+            (period_id, report_hash, amount),
+        );
+        Ok(())
+    }
+
+    // ── Configurable rounding (#44) ───────────────────────────
+
+    /// Set rounding mode for an offering's share calculations. Caller must be issuer.
+    pub fn set_rounding_mode(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        mode: RoundingMode,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        // Verify offering exists and issuer is current
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::LimitReached)?;
+
+        if current_issuer != issuer {
+            return Err(RevoraError::LimitReached);
+        }
+
+        issuer.require_auth();
+        let key = DataKey::RoundingMode(issuer, token);
+        env.storage().persistent().set(&key, &mode);
+        Ok(())
+    }
+
+    // ── Multi-period aggregated claims ───────────────────────────
+
+    /// Deposit revenue for a specific period of an offering.
+    ///
+    /// Transfers `amount` of `payment_token` from `issuer` to the contract. The platform
+    /// fee (`get_offering_fee_bps`) is withheld into the fee vault for
+    /// `payment_token`; the period's revenue is the remainder.
+    /// The payment token is locked per offering on first deposit; subsequent
+    /// deposits must use the same payment token.
+    /// Periods are kept in `period_id` order, so a late deposit for an older period is
+    /// claimed before newer ones, unless a holder has already claimed past that point.
+    /// Issuer authorization is bound to `(token, payment_token, amount, period_id)`.
+    /// When the period was already reported, the reported amount is recorded
+    /// (`get_period_accounting`) and a deposit whose net of the platform fee is below
+    /// `reported * revenue_share_bps / 10_000` publishes `rev_short`, or fails with
+    /// `DepositBelowReport` under `ContractConfig.reject_underpayment`, so holders are
+    /// owed their full share after the fee.
+    ///
+    /// Contract issuers (e.g. a DAO treasury) calling this directly satisfy the issuer
+    /// check as invoker, but the nested `transfer(issuer, contract, amount)` on the
+    /// payment token needs its own authorization: call `authorize_as_current_contract`
+    /// for it before invoking `deposit_revenue`.
+    pub fn deposit_revenue(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        payment_token: Address,
+        amount: i128,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+
+        // Verify offering exists and issuer is current
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+
+        Self::require_offering_open(&env, &token)?;
+        Self::require_offering_not_paused(&env, &token)?;
+
+        // Verify offering exists
+        let offering = Self::get_offering(env.clone(), issuer.clone(), token.clone())
+            .ok_or(RevoraError::OfferingNotFound)?;
+        if offering.payout_asset != payment_token {
+            return Err(RevoraError::PayoutAssetMismatch);
+        }
+
+        // Bind the signature to the exact amount and period being funded.
+        issuer.require_auth_for_args(
+            (token.clone(), payment_token.clone(), amount, period_id).into_val(&env),
+        );
+        Self::require_on_schedule(&env, &token, period_id)?;
+
+        // Check period not already deposited
+        let rev_key = DataKey::PeriodRevenue(token.clone(), period_id);
+        if env.storage().persistent().has(&rev_key) {
+            return Err(RevoraError::PeriodAlreadyDeposited);
+        }
+
+        // Withhold the platform fee; holders share the remainder.
+        let fee_bps = Self::get_offering_fee_bps(env.clone(), token.clone());
+        let fee = math::bps_of(amount, fee_bps);
+
+        // Hold what holders receive against their share of the reported revenue.
+        let reported = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Map<u64, (i128, u64)>>(&DataKey::RevenueReports(
+                issuer.clone(),
+                token.clone(),
+            ))
+            .and_then(|reports| reports.get(period_id))
+            .map(|(reported, _)| reported);
+        if let Some(reported) = reported {
+            let expected = math::bps_of(reported, offering.revenue_share_bps);
+            if amount - fee < expected {
+                if env
+                    .storage()
+                    .persistent()
+                    .has(&EscrowKey::RejectUnderpayment)
+                {
+                    return Err(RevoraError::DepositBelowReport);
+                }
+                env.events().publish(
+                    (EVENT_REV_SHORT, issuer.clone(), token.clone()),
+                    (period_id, expected, amount - fee),
+                );
+            }
+            env.storage()
+                .persistent()
+                .set(&EscrowKey::Reported(token.clone(), period_id), &reported);
+        }
+
+        // Store or validate payment token for this offering
+        let pt_key = DataKey::PaymentToken(token.clone());
+        if let Some(existing_pt) = env.storage().persistent().get::<DataKey, Address>(&pt_key) {
+            if existing_pt != payment_token {
+                return Err(RevoraError::PaymentTokenMismatch);
+            }
+        } else {
+            env.storage().persistent().set(&pt_key, &payment_token);
+        }
+
+        // Transfer tokens from issuer to contract
+        let contract_addr = env.current_contract_address();
+        token::Client::new(&env, &payment_token).transfer(&issuer, &contract_addr, &amount);
+
+        if fee > 0 {
+            // The referrer's cut comes out of the platform fee, not the holders' share.
+            let mut platform_fee = fee;
+            if let Some((referrer, referral_bps)) = Self::get_referrer(env.clone(), token.clone()) {
+                let cut = math::bps_of(fee, referral_bps);
+                if cut > 0 {
+                    let ref_key = FeeKey::ReferralAccrued(referrer.clone(), payment_token.clone());
+                    let accrued: i128 = env.storage().persistent().get(&ref_key).unwrap_or(0);
+                    env.storage().persistent().set(&ref_key, &(accrued + cut));
+                    env.events().publish(
+                        (EVENT_REFERRAL_ACCRUED, referrer),
+                        (token.clone(), period_id, cut),
+                    );
+                    platform_fee -= cut;
+                }
+            }
+            let fee_key = FeeKey::Accrued(payment_token.clone());
+            let accrued: i128 = env.storage().persistent().get(&fee_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&fee_key, &(accrued + platform_fee));
+            env.events().publish(
+                (EVENT_FEE_ACCRUED, payment_token.clone()),
+                (token.clone(), period_id, platform_fee),
+            );
+            env.storage()
+                .persistent()
+                .set(&EscrowKey::Fee(token.clone(), period_id), &fee);
+        }
+
+        // Store period revenue
+        env.storage().persistent().set(&rev_key, &(amount - fee));
+        if let Some(supply) = Self::get_balance_mode(env.clone(), token.clone()) {
+            env.storage().persistent().set(
+                &SnapshotKey::PeriodSupply(token.clone(), period_id),
+                &supply,
+            );
+        }
+
+        // Store deposit timestamp for time-delayed claims (#27)
+        let deposit_time = env.ledger().timestamp();
+        let time_key = DataKey::PeriodDepositTime(token.clone(), period_id);
+        env.storage().persistent().set(&time_key, &deposit_time);
+
+        // Insert into the indexed period list in period_id order
+        let count_key = DataKey::PeriodCount(token.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        Self::insert_period_entry(&env, &token, period_id, count);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        Self::extend_offering_ttl(&env, &issuer, &token);
+        Self::update_metrics(&env, |m| m.periods = m.periods.saturating_add(1));
+
+        if Self::is_event_versioning_enabled(env.clone()) {
+            env.events().publish(
+                (EVENT_REV_DEPOSIT_V2, issuer.clone(), token.clone()),
+                DepositedV2 {
+                    payment_token: payment_token.clone(),
+                    period_id,
+                    amount,
+                    deposited_at: deposit_time,
+                    currency: Self::get_accounting_currency(env.clone(), token.clone()),
+                },
+            );
+        }
+        env.events().publish(
+            (EVENT_REV_DEPOSIT, issuer, token),
+            (payment_token, amount, period_id),
+        );
+        Ok(())
+    }
+
+    /// Return a period's undistributed residual (truncation dust and unallocated share)
+    /// to the issuer and close the period. Allowed once every indexed holder with a share
+    /// has claimed past the period, or once `sweep_grace_secs` has elapsed since the
+    /// deposit. After a sweep the period pays nothing further, so holders who had not
+    /// claimed by the end of the grace window forfeit that period. Returns the amount swept.
+    pub fn sweep_residual(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        Self::require_offering_not_paused(&env, &token)?;
+
+        let period_idx: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodIndex(token.clone(), period_id))
+            .ok_or(RevoraError::InvalidState)?;
+        let swept_key = EscrowKey::Swept(token.clone(), period_id);
+        if env.storage().persistent().has(&swept_key) {
+            return Err(RevoraError::InvalidState);
+        }
+
+        let grace_secs: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SweepGraceSecs)
+            .unwrap_or(0);
+        let deposit_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodDepositTime(token.clone(), period_id))
+            .unwrap_or(0);
+        let grace_elapsed =
+            grace_secs > 0 && env.ledger().timestamp() >= deposit_time.saturating_add(grace_secs);
+        if !grace_elapsed && !Self::all_holders_settled(&env, &token, period_id, period_idx) {
+            return Err(RevoraError::PendingClaimsOutstanding);
+        }
+
+        let residual = Self::close_period(&env, &token, period_id, &issuer);
+        env.events()
+            .publish((EVENT_RESIDUAL_SWEPT, issuer, token), (period_id, residual));
+        Ok(residual)
+    }
+
+    // ── Claim expiry ───────────────────────────────────────────
+
+    /// Set how long after deposit an offering's periods stay claimable. Once the window
+    /// lapses the issuer may `reclaim_expired` whatever holders have not claimed.
+    /// 0 disables expiry. Applies to existing periods as well as new ones.
+    pub fn set_claim_expiry(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        expiry_secs: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let key = EscrowKey::ClaimExpiry(token.clone());
+        if expiry_secs == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &expiry_secs);
+        }
+        env.events()
+            .publish((EVENT_CLAIM_EXPIRY_SET, issuer, token), expiry_secs);
+        Ok(())
+    }
+
+    /// Redirect reclaimed revenue to a treasury address. `None` sends it back to the issuer.
+    pub fn set_reclaim_treasury(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        treasury: Option<Address>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+
+        let key = EscrowKey::ReclaimTreasury(token.clone());
+        match &treasury {
+            Some(addr) => env.storage().persistent().set(&key, addr),
+            None => env.storage().persistent().remove(&key),
+        }
+        env.events()
+            .publish((EVENT_RECLAIM_TREASURY_SET, issuer, token), treasury);
+        Ok(())
+    }
+
+    /// Reclaim a period's unclaimed revenue once the offering's claim window has lapsed.
+    /// Pays the reclaim treasury (or the issuer) and closes the period, so holders who
+    /// had not claimed forfeit it. Fails with `InvalidState` if no expiry is set, the
+    /// window is still open, or the period is already closed. Returns the amount reclaimed.
+    pub fn reclaim_expired(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        Self::require_offering_not_paused(&env, &token)?;
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::PeriodIndex(token.clone(), period_id))
+        {
+            return Err(RevoraError::InvalidState);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&EscrowKey::Swept(token.clone(), period_id))
+        {
+            return Err(RevoraError::InvalidState);
+        }
+        let expiry_secs = Self::get_claim_expiry(env.clone(), token.clone());
+        let deposit_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodDepositTime(token.clone(), period_id))
+            .unwrap_or(0);
+        if expiry_secs == 0 || env.ledger().timestamp() < deposit_time.saturating_add(expiry_secs) {
+            return Err(RevoraError::InvalidState);
+        }
+
+        let destination = Self::get_reclaim_treasury(env.clone(), token.clone())
+            .unwrap_or_else(|| issuer.clone());
+        let amount = Self::close_period(&env, &token, period_id, &destination);
+        env.events().publish(
+            (EVENT_EXPIRED_RECLAIMED, issuer, token),
+            (period_id, amount, destination),
+        );
+        Ok(amount)
+    }
+
+    // ── Push distribution ──────────────────────────────────────
+
+    /// Push a period's payouts from escrow to holders who never call `claim`.
+    ///
+    /// Pays at most the network profile's `distribute_batch` entries from `recipients`;
+    /// returns the index of the next unprocessed entry (cursor) or `None` when done. Each
+    /// amount must fit in the holder's remaining share entitlement for the period, counting
+    /// only the part unlocked so far when claims stream. Skipped
+    /// without error: holders `claim` would reject (blacklisted, locked up, missing KYC,
+    /// failing restrictions or not whitelisted), holders who already claimed the period,
+    /// and entries that would overpay. Pushed amounts are recorded per holder and
+    /// deducted from later `claim` payouts, so nothing is paid twice.
+    pub fn distribute_batch(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+        recipients: Vec<(Address, i128)>,
+    ) -> Result<Option<u32>, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        Self::require_offering_not_paused(&env, &token)?;
+
+        let period_idx: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodIndex(token.clone(), period_id))
+            .ok_or(RevoraError::InvalidState)?;
+        if Self::is_period_paused(env.clone(), token.clone(), period_id) {
+            return Err(RevoraError::PeriodPaused);
+        }
+        let deposit_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PeriodDepositTime(token.clone(), period_id))
+            .unwrap_or(0);
+        if Self::in_claim_delay(
+            &env,
+            &Self::claim_delay_schedule(&env, &token),
+            deposit_time,
+        ) {
+            return Err(RevoraError::ClaimDelayNotElapsed);
+        }
+
+        let payment_token: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PaymentToken(token.clone()))
+            .unwrap();
+        let pt_client = token::Client::new(&env, &payment_token);
+        let contract_addr = env.current_contract_address();
+        let elapsed = env.ledger().timestamp().saturating_sub(deposit_time);
+        let stream_secs = Self::get_stream_duration(env.clone(), token.clone());
+
+        let end = core::cmp::min(
+            recipients.len(),
+            Self::get_network_limits(env.clone()).distribute_batch,
+        );
+        let mut paid_count: u32 = 0;
+        let mut total_paid: i128 = 0;
+        for i in 0..end {
+            let (holder, amount) = recipients.get(i).unwrap();
+            if amount <= 0 {
+                return Err(RevoraError::InvalidAmount);
+            }
+            if Self::is_barred(&env, &token, &holder)
+                || Self::require_not_locked(&env, &token, &holder).is_err()
+                || Self::require_kyc(&env, &token, &holder).is_err()
+                || Self::require_restrictions_met(&env, &token, &holder).is_err()
+                || Self::require_whitelisted(&env, &token, &holder).is_err()
+            {
+                continue;
+            }
+            let claimed_idx: u32 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::LastClaimedIdx(token.clone(), holder.clone()))
+                .unwrap_or(0);
+            if claimed_idx > period_idx {
+                continue;
+            }
+            let share_bps = Self::get_holder_share(env.clone(), token.clone(), holder.clone());
+            let balance = Self::live_balance(&env, &token, &holder);
+            let (owed, _) = Self::streamed_payout(
+                &env,
+                &token,
+                period_id,
+                &holder,
+                share_bps,
+                balance,
+                elapsed,
+                stream_secs,
+            );
+            if amount > owed {
+                continue;
+            }
+            let paid_key = DataKey::PushPaid(token.clone(), period_id, holder.clone());
+            let paid: i128 = env.storage().persistent().get(&paid_key).unwrap_or(0);
+            env.storage().persistent().set(&paid_key, &(paid + amount));
+            Self::record_distributed(&env, &token, period_id, amount);
+            let recipient = Self::payout_recipient(&env, &token, &holder);
+            pt_client.transfer(&contract_addr, &recipient, &amount);
+            paid_count += 1;
+            total_paid += amount;
+        }
+
+        env.events().publish(
+            (EVENT_DISTRIBUTE_BATCH, token, period_id),
+            (paid_count, total_paid),
+        );
+        Ok(if end < recipients.len() {
+            Some(end)
+        } else {
+            None
+        })
+    }
+
+    // ── Merkle-root distribution ───────────────────────────────
+
+    /// Set the Merkle root for a period so holders claim with off-chain computed proofs
+    /// instead of on-chain `HolderShare` entries. Must be set before the period is
+    /// deposited, and only once. Share-based `claim` pays nothing for such periods.
+    ///
+    /// Leaf: `sha256(xdr((token, period_id, holder, amount)))`. Nodes hash the sorted
+    /// pair: `sha256(min(a, b) || max(a, b))`.
+    pub fn set_distribution_root(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+        merkle_root: BytesN<32>,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::PeriodRevenue(token.clone(), period_id))
+        {
+            return Err(RevoraError::PeriodAlreadyDeposited);
+        }
+        let root_key = MerkleKey::Root(token.clone(), period_id);
+        if env.storage().persistent().has(&root_key) {
+            return Err(RevoraError::InvalidState);
+        }
+        env.storage().persistent().set(&root_key, &merkle_root);
+        env.events()
+            .publish((EVENT_ROOT_SET, token, period_id), merkle_root);
+        Ok(())
+    }
+
+    /// Reuse `source_period_id`'s distribution root for `period_id` when holder composition
+    /// has not changed, instead of publishing a new tree. Holders prove their source-period
+    /// leaf and receive `amount * revenue / source_revenue` for this period. The source must
+    /// have its own root (not a reused one) and already be deposited; like
+    /// `set_distribution_root`, this must precede `period_id`'s deposit and runs once.
+    pub fn reuse_snapshot(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+        source_period_id: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        let store = env.storage().persistent();
+        if store.has(&DataKey::PeriodRevenue(token.clone(), period_id)) {
+            return Err(RevoraError::PeriodAlreadyDeposited);
+        }
+        let root_key = MerkleKey::Root(token.clone(), period_id);
+        if store.has(&root_key) {
+            return Err(RevoraError::InvalidState);
+        }
+        let root: BytesN<32> = store
+            .get(&MerkleKey::Root(token.clone(), source_period_id))
+            .ok_or(RevoraError::InvalidState)?;
+        if store.has(&MerkleKey::ReusedFrom(token.clone(), source_period_id))
+            || !store.has(&DataKey::PeriodRevenue(token.clone(), source_period_id))
+        {
+            return Err(RevoraError::InvalidState);
+        }
+
+        store.set(&root_key, &root);
+        store.set(
+            &MerkleKey::ReusedFrom(token.clone(), period_id),
+            &source_period_id,
+        );
+        env.events().publish(
+            (EVENT_SNAPSHOT_REUSED, token, period_id),
+            (source_period_id, root),
+        );
+        Ok(())
+    }
+
+    // ── Balance-derived shares ─────────────────────────────────
+
+    /// Opt `token` into balance-derived shares: later deposits record `total_supply`, and
+    /// `claim` pays each holder `token.balance(holder) * revenue / total_supply` for those
+    /// periods instead of using `set_holder_share`. The offering token must implement the
+    /// standard token interface. Update the supply before depositing when it changes; 0
+    /// turns the mode off. Balances are read at claim time, so this suits tokens that do
+    /// not move between deposit and claim; a payout beyond the period's revenue freezes
+    /// the offering like any other overdraw.
+    pub fn set_balance_mode(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        total_supply: i128,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        if total_supply < 0 {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let key = SnapshotKey::LiveSupply(token.clone());
+        if total_supply == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &total_supply);
+        }
+        env.events()
+            .publish((EVENT_BALANCE_MODE, issuer, token), total_supply);
+        Ok(())
+    }
+
+    // ── Balance-snapshot distribution ──────────────────────────
+
+    /// Upload holder balances for snapshot `snapshot_ref`, measured against
+    /// `total_supply`. Large holder sets upload in several calls of at most the network
+    /// `bulk_shares` limit (`LimitReached`); re-recording a holder replaces its balance.
+    /// Every call must pass the same `total_supply` and the recorded balances may not sum
+    /// above it (`InvalidAmount`). Fails with `InvalidState` once `set_period_snapshot`
+    /// has used the snapshot.
+    pub fn record_snapshot_balances(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        snapshot_ref: u64,
+        balances: Vec<(Address, i128)>,
+        total_supply: i128,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        if balances.len() > Self::get_network_limits(env.clone()).bulk_shares {
+            return Err(RevoraError::LimitReached);
+        }
+        if total_supply <= 0 {
+            return Err(RevoraError::ZeroTotalSupply);
+        }
+        let store = env.storage().persistent();
+        if store.has(&SnapshotKey::Sealed(token.clone(), snapshot_ref)) {
+            return Err(RevoraError::InvalidState);
+        }
+        let supply_key = SnapshotKey::Supply(token.clone(), snapshot_ref);
+        if let Some(supply) = store.get::<SnapshotKey, i128>(&supply_key) {
+            if supply != total_supply {
+                return Err(RevoraError::InvalidAmount);
+            }
+        }
+
+        let recorded_key = SnapshotKey::Recorded(token.clone(), snapshot_ref);
+        let mut recorded: i128 = store.get(&recorded_key).unwrap_or(0);
+        for (holder, balance) in balances.iter() {
+            if balance < 0 {
+                return Err(RevoraError::InvalidAmount);
+            }
+            let key = SnapshotKey::Balance(token.clone(), snapshot_ref, holder);
+            let old: i128 = store.get(&key).unwrap_or(0);
+            recorded = recorded - old + balance;
+            store.set(&key, &balance);
+        }
+        if recorded > total_supply {
+            return Err(RevoraError::InvalidAmount);
+        }
+        store.set(&recorded_key, &recorded);
+        store.set(&supply_key, &total_supply);
+        env.events().publish(
+            (EVENT_SNAPSHOT_BALANCES, token, snapshot_ref),
+            (balances.len(), total_supply),
+        );
+        Ok(())
+    }
+
+    /// Pay `period_id` pro-rata from snapshot `snapshot_ref` instead of `HolderShare` bps:
+    /// each holder claims `balance * revenue / total_supply` with `claim_snapshot`, and
+    /// share-based `claim` pays nothing for the period. Like `set_distribution_root`, this
+    /// must precede the period's deposit and runs once; the period may not also have a
+    /// Merkle root. Seals the snapshot against further uploads.
+    pub fn set_period_snapshot(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        period_id: u64,
+        snapshot_ref: u64,
+    ) -> Result<(), RevoraError> {
+        Self::require_not_frozen(&env)?;
+        issuer.require_auth();
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        let store = env.storage().persistent();
+        if store.has(&DataKey::PeriodRevenue(token.clone(), period_id)) {
+            return Err(RevoraError::PeriodAlreadyDeposited);
+        }
+        let period_key = SnapshotKey::ForPeriod(token.clone(), period_id);
+        if store.has(&period_key)
+            || store.has(&MerkleKey::Root(token.clone(), period_id))
+            || !store.has(&SnapshotKey::Supply(token.clone(), snapshot_ref))
+        {
+            return Err(RevoraError::InvalidState);
+        }
+        store.set(&period_key, &snapshot_ref);
+        store.set(&SnapshotKey::Sealed(token.clone(), snapshot_ref), &true);
+        env.events()
+            .publish((EVENT_SNAPSHOT_SET, token, period_id), snapshot_ref);
+        Ok(())
+    }
+
+    // ── Period-level pause ─────────────────────────────────────
+
+    /// Pause claims for a single period (e.g. while a dispute is evaluated). Admin only.
+    /// Claims stop at a paused period; earlier periods remain claimable. Fails with
+    /// `OfferingNotFound` for an unknown offering.
+    pub fn pause_period(
+        env: Env,
+        admin: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::PeriodPaused(token.clone(), period_id), &true);
+        env.events()
+            .publish((EVENT_PERIOD_PAUSED, token, period_id), admin);
+        Ok(())
+    }
+
+    /// Resume claims for a paused period. Admin only. Idempotent. Fails with
+    /// `OfferingNotFound` for an unknown offering.
+    pub fn unpause_period(
+        env: Env,
+        admin: Address,
+        token: Address,
+        period_id: u64,
+    ) -> Result<(), RevoraError> {
+        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
+        Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PeriodPaused(token.clone(), period_id));
+        env.events()
+            .publish((EVENT_PERIOD_UNPAUSED, token, period_id), admin);
+        Ok(())
+    }
+
+    /// Add `amount` of the offering's payment token to its bonus pool. Issuer only; the
+    /// payment token is fixed by the first deposit, so this fails with `NothingDeposited`
+    /// before then. Returns the new pool balance.
+    pub fn fund_bonus_pool(
+        env: Env,
+        issuer: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        if amount <= 0 {
+            return Err(RevoraError::InvalidAmount);
+        }
+        let payment_token: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PaymentToken(token.clone()))
+            .ok_or(RevoraError::NothingDeposited)?;
+        token::Client::new(&env, &payment_token).transfer(
+            &issuer,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let pool = Self::get_bonus_pool(env.clone(), token.clone()) + amount;
+        env.storage()
+            .persistent()
+            .set(&ClaimKey::BonusPool(token.clone()), &pool);
+        env.events()
+            .publish((EVENT_BONUS_FUNDED, issuer, token), amount);
+        Ok(pool)
+    }
+
+    /// Return the whole bonus pool to the issuer. Issuer only. Returns the amount (0 = no-op).
+    pub fn withdraw_bonus_pool(
+        env: Env,
+        issuer: Address,
+        token: Address,
+    ) -> Result<i128, RevoraError> {
+        Self::require_not_frozen(&env)?;
+        Self::require_offering_not_paused(&env, &token)?;
+        let current_issuer =
+            Self::get_current_issuer(&env, &token).ok_or(RevoraError::OfferingNotFound)?;
+        if current_issuer != issuer {
+            return Err(RevoraError::OfferingNotFound);
+        }
+        issuer.require_auth();
+        let pool = Self::get_bonus_pool(env.clone(), token.clone());
+        if pool == 0 {
+            return Ok(0);
+        }
+        let payment_token: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PaymentToken(token.clone()))
+            .unwrap();
+        env.storage()
+            .persistent()
+            .remove(&ClaimKey::BonusPool(token.clone()));
+        token::Client::new(&env, &payment_token).transfer(
+            &env.current_contract_address(),
+            &issuer,
+            &pool,
+        );
+        env.events()
+            .publish((EVENT_BONUS_WITHDRAWN, issuer, token), pool);
+        Ok(pool)
+    }
+
+    // ── Revenue distribution calculation ───────────────────────────
+
+    /// Calculate the distribution amount for a token holder.
+    ///
+    /// This function computes the payout amount for a single holder using
+    /// fixed-point arithmetic with basis points (BPS) precision.
+    ///
+    /// Formula:
+    ///   distributable_revenue = total_revenue * revenue_share_bps / BPS_DENOMINATOR
+    ///   holder_payout = holder_balance * distributable_revenue / total_supply
+    ///
+    /// Rounding: Uses integer division which rounds down (floor).
+    /// This is conservative and ensures the contract never over-distributes.
+    ///
+    /// Errors: `ZeroTotalSupply` if `total_supply` is 0, `OfferingNotFound` if the
+    /// offering does not exist, `HolderBlacklisted` if `holder` is blacklisted,
+    /// `InvalidAmount` if `total_revenue * bps` or `holder_balance * distributable`
+    /// overflows.
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate_distribution(
+        env: Env,
+        caller: Address,
+        issuer: Address,
+        token: Address,
+        total_revenue: i128,
+        total_supply: i128,
+        holder_balance: i128,
+        holder: Address,
+    ) -> Result<i128, RevoraError> {
+        caller.require_auth();
+
+        if total_supply == 0 {
+            return Err(RevoraError::ZeroTotalSupply);
+        }
+
+        let offering = Self::get_offering(env.clone(), issuer.clone(), token.clone())
+            .ok_or(RevoraError::OfferingNotFound)?;
+
+        if Self::is_blacklisted(env.clone(), token.clone(), holder.clone()) {
+            return Err(RevoraError::HolderBlacklisted);
+        }
+
+        if total_revenue == 0 || holder_balance == 0 {
+            let payout = 0i128;
+            env.events().publish(
+                (EVENT_DIST_CALC, token.clone(), holder.clone()),
+                (
+                    total_revenue,
+                    total_supply,
+                    holder_balance,
+                    offering.revenue_share_bps,
+                    payout,
+                ),
+            );
+            return Ok(payout);
+        }
+
+        let payout = math::holder_payout(
+            total_revenue,
+            offering.revenue_share_bps,
+            holder_balance,
+            total_supply,
+        )
+        .ok_or(RevoraError::InvalidAmount)?;
+
+        env.events().publish(
+            (EVENT_DIST_CALC, token, holder),
+            (
+                total_revenue,
+                total_supply,
+                holder_balance,
+                offering.revenue_share_bps,
+                payout,
+            ),
+        );
+
+        Ok(payout)
+    }
+}
@@ -0,0 +1,141 @@
+//! Seeding helpers for integration tests, compiled only with the `testutils` feature (and in
+//! this crate's own tests).
+//!
+//! `OfferingSeed` drives the regular entrypoints through a `RevoraRevenueShareClient`, so
+//! seeded state is indistinguishable from state built call by call. Seeding is not an
+//! entrypoint itself: Soroban authorizes an address once per call frame, so one entrypoint
+//! cannot chain several issuer-authorized ones. The environment must accept the issuer's
+//! authorization, e.g. with `env.mock_all_auths()`.
+
+use soroban_sdk::{testutils::Address as _, token, Address, Vec};
+
+use crate::RevoraRevenueShareClient;
+
+/// Builder for a seeded offering: `holders` holders sharing 10_000 bps evenly and `periods`
+/// deposited periods (IDs 1..=periods) behind an optional claim delay.
+#[derive(Clone, Debug)]
+pub struct OfferingSeed {
+    pub revenue_share_bps: u32,
+    pub holders: u32,
+    pub periods: u32,
+    pub amount_per_period: i128,
+    pub claim_delay_secs: u64,
+}
+
+/// What `OfferingSeed::seed` created.
+#[derive(Clone, Debug)]
+pub struct SeededOffering {
+    pub issuer: Address,
+    pub token: Address,
+    /// Stellar asset contract the deposits were paid in; the issuer is its admin.
+    pub payment_token: Address,
+    /// Offering ID from `register_offering`.
+    pub id: u64,
+    /// Holders in share-assignment order; the first one also holds the bps remainder.
+    pub holders: Vec<Address>,
+}
+
+impl Default for OfferingSeed {
+    fn default() -> Self {
+        Self {
+            revenue_share_bps: 10_000,
+            holders: 1,
+            periods: 1,
+            amount_per_period: 1_000,
+            claim_delay_secs: 0,
+        }
+    }
+}
+
+impl OfferingSeed {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn revenue_share_bps(mut self, bps: u32) -> Self {
+        self.revenue_share_bps = bps;
+        self
+    }
+
+    pub fn holders(mut self, count: u32) -> Self {
+        self.holders = count;
+        self
+    }
+
+    pub fn periods(mut self, count: u32) -> Self {
+        self.periods = count;
+        self
+    }
+
+    pub fn amount_per_period(mut self, amount: i128) -> Self {
+        self.amount_per_period = amount;
+        self
+    }
+
+    pub fn claim_delay_secs(mut self, secs: u64) -> Self {
+        self.claim_delay_secs = secs;
+        self
+    }
+
+    /// Register a fresh offering with generated issuer, token and holders, assign shares,
+    /// set the claim delay, mint the issuer exactly the deposits and deposit every period.
+    /// Panics on the first failing call, like any client call in a test.
+    pub fn seed(&self, client: &RevoraRevenueShareClient) -> SeededOffering {
+        let env = &client.env;
+        let issuer = Address::generate(env);
+        let token = Address::generate(env);
+        let payment_token = env
+            .register_stellar_asset_contract_v2(issuer.clone())
+            .address();
+
+        let registered =
+            client.register_offering(&issuer, &token, &self.revenue_share_bps, &payment_token);
+
+        let mut holders = Vec::new(env);
+        let mut shares = Vec::new(env);
+        if let Some(each) = 10_000u32.checked_div(self.holders) {
+            for i in 0..self.holders {
+                let holder = Address::generate(env);
+                let extra = if i == 0 { 10_000 % self.holders } else { 0 };
+                shares.push_back((holder.clone(), each + extra));
+                holders.push_back(holder);
+            }
+            client.set_holder_shares_bulk(&issuer, &token, &shares);
+        }
+        if self.claim_delay_secs > 0 {
+            client.set_claim_delay(&issuer, &token, &self.claim_delay_secs);
+        }
+
+        let seeded = SeededOffering {
+            issuer,
+            token,
+            payment_token,
+            id: registered.id,
+            holders,
+        };
+        seeded.seed_periods(client, self.periods, self.amount_per_period);
+        seeded
+    }
+}
+
+impl SeededOffering {
+    /// Mint and deposit `count` more periods of `amount` each. Period IDs continue from the
+    /// offering's period count, so this assumes periods were deposited as 1, 2, 3, ...
+    pub fn seed_periods(&self, client: &RevoraRevenueShareClient, count: u32, amount: i128) {
+        let first = client.get_period_count(&self.token) as u64 + 1;
+        let total = amount * count as i128;
+        if total > 0 {
+            token::StellarAssetClient::new(&client.env, &self.payment_token)
+                .mint(&self.issuer, &total);
+        }
+        for period_id in first..first + count as u64 {
+            client.deposit_revenue(
+                &self.issuer,
+                &self.token,
+                &self.payment_token,
+                &amount,
+                &period_id,
+            );
+        }
+    }
+}